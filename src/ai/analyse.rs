@@ -0,0 +1,84 @@
+use crate::brett::{SpielBrett, Spieler, Zug};
+
+use super::{Endspieldatenbank, Gewichte};
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ZugAnalyse {
+    pub zug: Zug,
+    pub brett: SpielBrett,
+    /// Bewertung aus Sicht des Spielers, der den Zug macht
+    pub bewertung: i32,
+    /// Die erwartete Fortsetzung, beginnend mit `brett`
+    pub hauptvariante: Vec<SpielBrett>,
+}
+
+impl SpielBrett {
+    /// Bewertet jeden möglichen Zug von `am_zug` mit einer eigenen Suche. Die Züge sind vom
+    /// besten zum schlechtesten sortiert.
+    pub fn analysiere(
+        &self,
+        am_zug: Spieler,
+        max_depth: u32,
+        datenbank: Option<&Endspieldatenbank>,
+        gewichte: &Gewichte,
+    ) -> Vec<ZugAnalyse> {
+        let mut analysen: Vec<ZugAnalyse> = self
+            .get_possible_zuege(am_zug)
+            .into_iter()
+            .map(|(zug, brett)| {
+                let (bewertung, fortsetzung) = if max_depth <= 1 {
+                    let bewertung =
+                        match datenbank.and_then(|datenbank| datenbank.get_wert(&brett, !am_zug)) {
+                            Some(wert) => wert.to_bewertung().saturating_neg(),
+                            None => gewichte.bewerte(&brett, am_zug),
+                        };
+                    (bewertung, Vec::new())
+                } else {
+                    let ergebnis =
+                        brett.suche_mit(max_depth - 1, !am_zug, datenbank, None, gewichte);
+                    (ergebnis.bewertung.saturating_neg(), ergebnis.hauptvariante)
+                };
+
+                let mut hauptvariante = vec![brett.clone()];
+                hauptvariante.extend(fortsetzung);
+                ZugAnalyse {
+                    zug,
+                    brett,
+                    bewertung,
+                    hauptvariante,
+                }
+            })
+            .collect();
+        analysen.sort_by_key(|analyse| std::cmp::Reverse(analyse.bewertung));
+        analysen
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_analysiere() {
+        let brett = SpielBrett::parse(concat!(
+            "_ _ _ _ \n",
+            " _ _ _ _\n",
+            "_ m _ _ \n",
+            " _ _ _ _\n",
+            "_ _ c _ \n",
+            " _ _ _ _\n",
+            "_ _ _ _ \n",
+            " _ _ _ _",
+        ))
+        .unwrap();
+
+        let analysen = brett.analysiere(Spieler::Weiss, 2, None, &Gewichte::default());
+        assert_eq!(analysen.len(), 2);
+        assert!(analysen
+            .iter()
+            .all(|analyse| analyse.hauptvariante.len() == 2));
+        assert_eq!(analysen[0].zug.to_notation(), "19-15");
+        assert_eq!(analysen[0].bewertung, 0);
+        assert!(analysen[1].bewertung < 0);
+    }
+}
@@ -0,0 +1,640 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use crate::brett::{
+    get_damen_endspiel_limit, zaehle_damen_endspiel, Feld, Position, SpielBrett, Spieler,
+};
+
+const ANZAHL_FELDER: usize = SpielBrett::SIZE * SpielBrett::SIZE / 2;
+const MAGIC: &[u8; 8] = b"DAMEEDB5";
+
+const BINOMIAL: [[usize; ANZAHL_FELDER + 1]; ANZAHL_FELDER + 1] = {
+    let mut tabelle = [[0; ANZAHL_FELDER + 1]; ANZAHL_FELDER + 1];
+    let mut n = 0;
+    while n <= ANZAHL_FELDER {
+        tabelle[n][0] = 1;
+        let mut k = 1;
+        while k <= n {
+            tabelle[n][k] = tabelle[n - 1][k - 1] + tabelle[n - 1][k];
+            k += 1;
+        }
+        n += 1;
+    }
+    tabelle
+};
+
+/// Das Ergebnis einer Stellung bei perfektem Spiel aus Sicht des Spielers am Zug. Die
+/// Entfernung wird in Halbzügen bis zum Spielende gezählt. Im Endspiel mit einer einzelnen
+/// Dame zählt sie nur bis zum nächsten Schlagen oder zur nächsten Dame, weil die Partie nach
+/// dem Limit von `Remisregeln::damen_endspiel` unentschieden ist. Gewinne, die länger
+/// brauchen, sind dort Remis.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Wert {
+    Gewinn(u32),
+    Verlust(u32),
+    Remis,
+}
+
+impl Wert {
+    pub const GEWINN_BEWERTUNG: i32 = 100_000;
+
+    /// Bewertung für die Suche: schnelle Gewinne und langsame Verluste sind besser.
+    pub fn to_bewertung(self) -> i32 {
+        match self {
+            Self::Gewinn(entfernung) => Self::GEWINN_BEWERTUNG - entfernung as i32,
+            Self::Verlust(entfernung) => -Self::GEWINN_BEWERTUNG + entfernung as i32,
+            Self::Remis => 0,
+        }
+    }
+
+    const MAX_ENTFERNUNG: u32 = 0x7fff;
+
+    // 0: Remis, 1 bis 0x7fff: Gewinn, ab 0x8000: Verlust. So weit entfernte Enden kommen mit
+    // wenigen Figuren nicht vor, die Begrenzung verhindert nur einen Überlauf.
+    fn to_u16(self) -> u16 {
+        match self {
+            Self::Remis => 0,
+            Self::Gewinn(entfernung) => entfernung.clamp(1, Self::MAX_ENTFERNUNG) as u16,
+            Self::Verlust(entfernung) => 0x8000 + entfernung.min(Self::MAX_ENTFERNUNG) as u16,
+        }
+    }
+
+    fn from_u16(zahl: u16) -> Self {
+        match zahl {
+            0 => Self::Remis,
+            1..=0x7fff => Self::Gewinn(zahl as u32),
+            _ => Self::Verlust(zahl as u32 - 0x8000),
+        }
+    }
+}
+
+/// Anzahl der Figuren jeder Art in der Reihenfolge von `Feld::FIGUREN`
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Material(pub [u8; 4]);
+
+impl Material {
+    pub fn figuren(self) -> usize {
+        self.0.iter().map(|&anzahl| anzahl as usize).sum()
+    }
+
+    fn steine(self) -> usize {
+        self.0[0] as usize + self.0[2] as usize
+    }
+
+    /// Jede Gruppe steht auf den Feldern, die die Gruppen davor frei lassen
+    fn gruppen_size(self) -> [usize; 4] {
+        let mut frei = ANZAHL_FELDER;
+        self.0.map(|anzahl| {
+            let size = BINOMIAL[frei][anzahl as usize];
+            frei -= anzahl as usize;
+            size
+        })
+    }
+
+    /// Anzahl der Indizes, einschließlich Stellungen mit Steinen auf der Grundlinie des Gegners
+    fn size(self) -> usize {
+        self.gruppen_size().iter().product::<usize>() * 2
+    }
+
+    /// Alle Materialverteilungen mit höchstens `max_figuren` Figuren, bei denen beide
+    /// Spieler noch Figuren haben. Die Reihenfolge ist so gewählt, dass jede Stellung nur
+    /// in Stellungen mit vorher berechnetem Material übergehen kann.
+    fn alle(max_figuren: usize) -> Vec<Self> {
+        let mut result = Vec::new();
+        for gesamt in 2..=max_figuren {
+            for a in 0..=gesamt {
+                for b in 0..=gesamt - a {
+                    for c in 0..=gesamt - a - b {
+                        let d = gesamt - a - b - c;
+                        if a + b == 0 || c + d == 0 {
+                            continue;
+                        }
+                        result.push(Self([a as u8, b as u8, c as u8, d as u8]));
+                    }
+                }
+            }
+        }
+        result.sort_by_key(|material| (material.figuren(), material.steine()));
+        result
+    }
+}
+
+fn rank(felder: &[usize]) -> usize {
+    felder
+        .iter()
+        .enumerate()
+        .map(|(index, &feld)| BINOMIAL[feld][index + 1])
+        .sum()
+}
+
+fn unrank(mut rank: usize, anzahl: usize, felder: &mut Vec<usize>) {
+    let start = felder.len();
+    for index in (1..=anzahl).rev() {
+        let mut feld = index - 1;
+        while BINOMIAL[feld + 1][index] <= rank {
+            feld += 1;
+        }
+        rank -= BINOMIAL[feld][index];
+        felder.push(feld);
+    }
+    felder[start..].reverse();
+}
+
+fn get_index(brett: &SpielBrett, am_zug: Spieler) -> (Material, usize) {
+    let mut gruppen: [Vec<usize>; 4] = Default::default();
+    for feld in 0..ANZAHL_FELDER {
+        if let Some(figur) = brett
+            .get(Position::from_feld_nummer(feld + 1).unwrap())
+            .figur_index()
+        {
+            gruppen[figur].push(feld);
+        }
+    }
+    let material = Material(gruppen.each_ref().map(|gruppe| gruppe.len() as u8));
+
+    // Jedes Feld wird unter den Feldern gezählt, die die Gruppen davor frei lassen
+    let mut belegt = [false; ANZAHL_FELDER];
+    let mut index = 0;
+    for (gruppe, size) in gruppen.iter_mut().zip(material.gruppen_size()) {
+        let felder = gruppe.clone();
+        for feld in gruppe.iter_mut() {
+            *feld -= belegt[..*feld].iter().filter(|&&belegt| belegt).count();
+        }
+        index = index * size + rank(gruppe);
+        for feld in felder {
+            belegt[feld] = true;
+        }
+    }
+    let am_zug = match am_zug {
+        Spieler::Schwarz => 0,
+        Spieler::Weiss => 1,
+    };
+    (material, index * 2 + am_zug)
+}
+
+fn get_stellung(material: Material, index: usize) -> Option<(SpielBrett, Spieler)> {
+    let am_zug = match index % 2 {
+        0 => Spieler::Schwarz,
+        _ => Spieler::Weiss,
+    };
+    let mut index = index / 2;
+    let mut ranks = [0; 4];
+    for (gruppe, size) in material.gruppen_size().into_iter().enumerate().rev() {
+        ranks[gruppe] = index % size;
+        index /= size;
+    }
+
+    let mut brett = SpielBrett::default();
+    let mut felder = Vec::new();
+    for (gruppe, figur) in Feld::FIGUREN.into_iter().enumerate() {
+        felder.clear();
+        unrank(ranks[gruppe], material.0[gruppe] as usize, &mut felder);
+        // Die Felder der Gruppe zählen nur die Felder, die die Gruppen davor frei lassen
+        let mut freie_felder = [0; ANZAHL_FELDER];
+        let mut anzahl_frei = 0;
+        for feld in 0..ANZAHL_FELDER {
+            if brett.get(Position::from_feld_nummer(feld + 1).unwrap()) == Feld::Leer {
+                freie_felder[anzahl_frei] = feld;
+                anzahl_frei += 1;
+            }
+        }
+        for &frei in &felder {
+            let position = Position::from_feld_nummer(freie_felder[frei] + 1).unwrap();
+            if let Feld::Stein(spieler) = figur {
+                if position.zeile == spieler.dame_zeile() {
+                    return None;
+                }
+            }
+            brett.set(position, figur);
+        }
+    }
+    Some((brett, am_zug))
+}
+
+fn has_figuren(brett: &SpielBrett, spieler: Spieler) -> bool {
+    !brett.has_won(!spieler)
+}
+
+/// Datenbank mit dem Ergebnis jeder Stellung mit wenigen Figuren.
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct Endspieldatenbank {
+    tabellen: HashMap<Material, Vec<u16>>,
+}
+
+impl Endspieldatenbank {
+    /// Berechnet die Datenbank für alle Stellungen mit höchstens `max_figuren` Figuren.
+    /// `fortschritt` wird vor jeder Materialverteilung aufgerufen.
+    pub fn generate(max_figuren: usize, mut fortschritt: impl FnMut(Material)) -> Self {
+        let mut datenbank = Self::default();
+        for material in Material::alle(max_figuren) {
+            fortschritt(material);
+            let tabelle = datenbank.generate_tabelle(material);
+            datenbank.tabellen.insert(material, tabelle);
+        }
+        datenbank
+    }
+
+    /// Der Wert eines Zuges nach `nachfolger` aus Sicht von `am_zug`, aus dem Wert der Stellung
+    /// danach. `None`, wenn die Stellung danach dasselbe Material hat und ihr Wert noch fehlt.
+    /// Im Damenendspiel mit `limit` endet die Zählung beim Wechsel des Materials.
+    fn get_zug_wert(
+        &self,
+        material: Material,
+        limit: Option<u32>,
+        nachfolger: &SpielBrett,
+        am_zug: Spieler,
+        werte: impl Fn(usize) -> Option<Wert>,
+    ) -> Option<Wert> {
+        if !has_figuren(nachfolger, !am_zug) {
+            return Some(Wert::Gewinn(1));
+        }
+        let (nachfolger_material, nachfolger_index) = get_index(nachfolger, !am_zug);
+        let (wert, extern_wert) = if nachfolger_material == material {
+            (werte(nachfolger_index)?, false)
+        } else {
+            let tabelle = &self.tabellen[&nachfolger_material];
+            (Wert::from_u16(tabelle[nachfolger_index]), true)
+        };
+        let entfernung = |entfernung: u32| match limit {
+            Some(_) if extern_wert => 1,
+            _ => entfernung + 1,
+        };
+        Some(match wert {
+            Wert::Gewinn(nachfolger_entfernung) => Wert::Verlust(entfernung(nachfolger_entfernung)),
+            Wert::Verlust(nachfolger_entfernung) => Wert::Gewinn(entfernung(nachfolger_entfernung)),
+            Wert::Remis => Wert::Remis,
+        })
+    }
+
+    // Für jede Entfernung werden die Züge aller offenen Stellungen neu erzeugt, statt für jede
+    // Stellung ihre Nachfolger zu speichern. So braucht die Tabelle nur drei Bytes pro Stellung.
+    fn generate_tabelle(&self, material: Material) -> Vec<u16> {
+        let size = material.size();
+        let limit = (0..size)
+            .find_map(|index| get_stellung(material, index))
+            .and_then(|(brett, _)| get_damen_endspiel_limit(&brett));
+        let mut werte = vec![Wert::Remis.to_u16(); size];
+        let mut bekannt = vec![false; size];
+        let mut max_extern_entfernung = 0;
+
+        for index in 0..size {
+            let Some((brett, am_zug)) = get_stellung(material, index) else {
+                bekannt[index] = true;
+                continue;
+            };
+
+            // Wer nicht mehr ziehen kann, hat verloren
+            let moves = brett.get_possible_moves(am_zug);
+            if moves.is_empty() {
+                werte[index] = Wert::Verlust(0).to_u16();
+                bekannt[index] = true;
+                continue;
+            }
+            for nachfolger in moves {
+                let wert = self.get_zug_wert(material, limit, &nachfolger, am_zug, |_| None);
+                if let Some(Wert::Gewinn(entfernung) | Wert::Verlust(entfernung)) = wert {
+                    max_extern_entfernung = max_extern_entfernung.max(entfernung);
+                }
+            }
+        }
+
+        let mut entfernung = 1;
+        loop {
+            let mut changed = false;
+            for index in 0..size {
+                if bekannt[index] {
+                    continue;
+                }
+                let (brett, am_zug) = get_stellung(material, index).unwrap();
+
+                let mut gewinn = false;
+                let mut alle_verloren = true;
+                let mut max_verlust = 0;
+                for nachfolger in brett.get_possible_moves(am_zug) {
+                    let wert = self.get_zug_wert(material, limit, &nachfolger, am_zug, |index| {
+                        bekannt[index].then(|| Wert::from_u16(werte[index]))
+                    });
+                    match wert {
+                        Some(Wert::Gewinn(zug_entfernung)) if zug_entfernung == entfernung => {
+                            gewinn = true;
+                        }
+                        Some(Wert::Verlust(zug_entfernung)) => {
+                            max_verlust = max_verlust.max(zug_entfernung)
+                        }
+                        _ => alle_verloren = false,
+                    }
+                }
+
+                if gewinn {
+                    werte[index] = Wert::Gewinn(entfernung).to_u16();
+                    bekannt[index] = true;
+                    changed = true;
+                } else if alle_verloren && max_verlust == entfernung {
+                    werte[index] = Wert::Verlust(entfernung).to_u16();
+                    bekannt[index] = true;
+                    changed = true;
+                }
+            }
+
+            if !changed && entfernung > max_extern_entfernung + 1 {
+                break;
+            }
+            entfernung += 1;
+        }
+
+        // Was erst nach dem Limit entschieden ist, endet vorher remis
+        if let Some(limit) = limit {
+            for wert in &mut werte {
+                if let Wert::Gewinn(entfernung) | Wert::Verlust(entfernung) = Wert::from_u16(*wert)
+                {
+                    if entfernung > limit {
+                        *wert = Wert::Remis.to_u16();
+                    }
+                }
+            }
+        }
+        werte
+    }
+
+    pub fn max_figuren(&self) -> usize {
+        self.tabellen
+            .keys()
+            .map(|material| material.figuren())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Das Ergebnis der Stellung, falls ihr Material in der Datenbank enthalten ist.
+    pub fn get_wert(&self, brett: &SpielBrett, am_zug: Spieler) -> Option<Wert> {
+        if !has_figuren(brett, am_zug) {
+            return Some(Wert::Verlust(0));
+        }
+        let (material, index) = get_index(brett, am_zug);
+        let tabelle = self.tabellen.get(&material)?;
+        Some(Wert::from_u16(tabelle[index]))
+    }
+
+    /// Wie `get_wert`, wenn im Endspiel mit einer einzelnen Dame schon `halbzuege` Halbzüge
+    /// ohne Schlagen gespielt sind. Was bis zum Limit nicht mehr entschieden wird, ist Remis.
+    pub fn get_wert_nach(
+        &self,
+        brett: &SpielBrett,
+        am_zug: Spieler,
+        halbzuege: u32,
+    ) -> Option<Wert> {
+        let wert = self.get_wert(brett, am_zug)?;
+        let limit = get_damen_endspiel_limit(brett);
+        Some(match wert {
+            Wert::Gewinn(entfernung) | Wert::Verlust(entfernung)
+                if entfernung > 0 && limit.is_some_and(|limit| entfernung + halbzuege > limit) =>
+            {
+                Wert::Remis
+            }
+            wert => wert,
+        })
+    }
+
+    /// Wählt den Zug, der am schnellsten gewinnt oder am längsten verliert, wenn im Endspiel mit
+    /// einer einzelnen Dame schon `halbzuege` Halbzüge gespielt sind. `None`, wenn die Stellung
+    /// fehlt oder kein Zug möglich ist.
+    pub fn get_best_move(
+        &self,
+        brett: &SpielBrett,
+        am_zug: Spieler,
+        halbzuege: u32,
+    ) -> Option<(SpielBrett, Wert)> {
+        let (material, _) = get_index(brett, am_zug);
+        self.tabellen.get(&material)?;
+        let limit = get_damen_endspiel_limit(brett);
+        let mut best: Option<(Wert, SpielBrett)> = None;
+        for nachfolger in brett.get_possible_moves(am_zug) {
+            let wert = self.get_zug_wert(material, limit, &nachfolger, am_zug, |_| {
+                let halbzuege = zaehle_damen_endspiel(brett, &nachfolger, halbzuege);
+                self.get_wert_nach(&nachfolger, !am_zug, halbzuege)
+            })?;
+            if best
+                .as_ref()
+                .is_none_or(|(best_wert, _)| wert.to_bewertung() > best_wert.to_bewertung())
+            {
+                best = Some((wert, nachfolger));
+            }
+        }
+        best.map(|(wert, brett)| (brett, wert))
+    }
+
+    // Dateiformat: MAGIC, Anzahl der Tabellen (u32), danach für jede Tabelle das Material
+    // (4 Bytes), die Länge (u32) und ein u16 pro Stellung. Zahlen sind Little Endian.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut materialien: Vec<_> = self.tabellen.keys().copied().collect();
+        materialien.sort_by_key(|material| material.0);
+
+        let mut result = Vec::new();
+        result.extend_from_slice(MAGIC);
+        result.extend_from_slice(&(materialien.len() as u32).to_le_bytes());
+        for material in materialien {
+            let tabelle = &self.tabellen[&material];
+            result.extend_from_slice(&material.0);
+            result.extend_from_slice(&(tabelle.len() as u32).to_le_bytes());
+            for wert in tabelle {
+                result.extend_from_slice(&wert.to_le_bytes());
+            }
+        }
+        result
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        fn read<'a>(bytes: &mut &'a [u8], len: usize) -> Option<&'a [u8]> {
+            if bytes.len() < len {
+                return None;
+            }
+            let (result, rest) = bytes.split_at(len);
+            *bytes = rest;
+            Some(result)
+        }
+        fn read_u32(bytes: &mut &[u8]) -> Option<usize> {
+            Some(u32::from_le_bytes(read(bytes, 4)?.try_into().ok()?) as usize)
+        }
+
+        let mut bytes = bytes;
+        if read(&mut bytes, MAGIC.len())? != MAGIC {
+            return None;
+        }
+        let mut datenbank = Self::default();
+        for _ in 0..read_u32(&mut bytes)? {
+            let material = Material(read(&mut bytes, 4)?.try_into().ok()?);
+            let len = read_u32(&mut bytes)?;
+            if len != material.size() {
+                return None;
+            }
+            let tabelle = read(&mut bytes, len * 2)?
+                .chunks_exact(2)
+                .map(|zahl| u16::from_le_bytes([zahl[0], zahl[1]]))
+                .collect();
+            datenbank.tabellen.insert(material, tabelle);
+        }
+        if !bytes.is_empty() {
+            return None;
+        }
+        Some(datenbank)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Option<Self> {
+        Self::from_bytes(&fs::read(path).ok()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index() {
+        let brett = SpielBrett::parse(concat!(
+            "_ _ _ _ \n",
+            " _ M _ _\n",
+            "_ _ _ _ \n",
+            " _ c _ _\n",
+            "_ _ _ _ \n",
+            " _ _ m _\n",
+            "_ _ _ _ \n",
+            " C _ _ C",
+        ))
+        .unwrap();
+        let (material, index) = get_index(&brett, Spieler::Weiss);
+        assert_eq!(material, Material([1, 1, 1, 2]));
+        assert_eq!(get_stellung(material, index), Some((brett, Spieler::Weiss)));
+
+        // Figuren stehen nie auf demselben Feld, deshalb gehört jeder Index zu einer Stellung
+        let material = Material([0, 1, 0, 2]);
+        assert_eq!(material.size(), 32 * 31 * 30 / 2 * 2);
+        for index in 0..material.size() {
+            let (brett, am_zug) = get_stellung(material, index).unwrap();
+            assert_eq!(get_index(&brett, am_zug), (material, index));
+        }
+    }
+
+    #[test]
+    fn test_wert() {
+        for wert in [
+            Wert::Remis,
+            Wert::Gewinn(1),
+            Wert::Gewinn(200),
+            Wert::Verlust(0),
+            Wert::Verlust(300),
+        ] {
+            assert_eq!(Wert::from_u16(wert.to_u16()), wert);
+        }
+    }
+
+    #[test]
+    fn test_endspieldatenbank() {
+        let datenbank = Endspieldatenbank::generate(2, |_| ());
+
+        // Die Dame schlägt den letzten Stein
+        let brett = SpielBrett::parse(concat!(
+            "_ _ _ _ \n",
+            " _ _ _ _\n",
+            "_ _ _ _ \n",
+            " _ m _ _\n",
+            "_ _ _ _ \n",
+            " _ _ _ _\n",
+            "_ _ _ _ \n",
+            " _ _ _ C",
+        ))
+        .unwrap();
+        assert_eq!(
+            datenbank.get_wert(&brett, Spieler::Weiss),
+            Some(Wert::Gewinn(1))
+        );
+
+        // Der Stein kann nur auf ein Feld ziehen, auf dem er geschlagen wird
+        let brett = SpielBrett::parse(concat!(
+            "_ _ _ _ \n",
+            " _ _ _ _\n",
+            "_ _ _ _ \n",
+            " _ _ _ m\n",
+            "_ _ _ _ \n",
+            " _ _ C _\n",
+            "_ _ _ _ \n",
+            " _ _ _ _",
+        ))
+        .unwrap();
+        assert_eq!(
+            datenbank.get_wert(&brett, Spieler::Schwarz),
+            Some(Wert::Verlust(2))
+        );
+
+        // Der Stein kann nicht mehr ziehen
+        let brett = SpielBrett::parse(concat!(
+            "_ _ _ m \n",
+            " _ _ _ c\n",
+            "_ _ _ _ \n",
+            " _ _ _ _\n",
+            "_ _ _ _ \n",
+            " _ _ _ _\n",
+            "_ _ _ _ \n",
+            " _ _ _ _",
+        ))
+        .unwrap();
+        assert_eq!(
+            datenbank.get_wert(&brett, Spieler::Weiss),
+            Some(Wert::Verlust(0))
+        );
+        assert_eq!(datenbank.get_best_move(&brett, Spieler::Weiss, 0), None);
+
+        // Im Endspiel mit einer einzelnen Dame gewinnt das Schlagen nur vor dem Limit
+        let brett = SpielBrett::parse(concat!(
+            "_ _ _ _ \n",
+            " _ _ _ _\n",
+            "_ _ _ _ \n",
+            " _ M _ _\n",
+            "_ _ _ _ \n",
+            " _ _ C _\n",
+            "_ _ _ _ \n",
+            " _ _ _ _",
+        ))
+        .unwrap();
+        assert_eq!(
+            datenbank.get_wert_nach(&brett, Spieler::Weiss, 9),
+            Some(Wert::Gewinn(1))
+        );
+        assert_eq!(
+            datenbank.get_wert_nach(&brett, Spieler::Weiss, 10),
+            Some(Wert::Remis)
+        );
+
+        // Jede Stellung muss zu den Werten ihrer Nachfolger passen
+        for material in Material::alle(2) {
+            for index in 0..material.size() {
+                let Some((brett, am_zug)) = get_stellung(material, index) else {
+                    continue;
+                };
+                let wert = datenbank.get_wert(&brett, am_zug).unwrap();
+                let Some((best, zug_wert)) = datenbank.get_best_move(&brett, am_zug, 0) else {
+                    assert_eq!(wert, Wert::Verlust(0));
+                    continue;
+                };
+                assert_eq!(zug_wert, wert);
+                let halbzuege = zaehle_damen_endspiel(&brett, &best, 0);
+                let nachfolger_wert = datenbank.get_wert_nach(&best, !am_zug, halbzuege).unwrap();
+                // Im Damenendspiel zählt die Entfernung nur bis zum Schlagen
+                let entfernung = |entfernung: u32| match get_damen_endspiel_limit(&brett) {
+                    Some(_) if halbzuege == 0 => 1,
+                    _ => entfernung + 1,
+                };
+                assert_eq!(
+                    match nachfolger_wert {
+                        Wert::Gewinn(nachfolger) => Wert::Verlust(entfernung(nachfolger)),
+                        Wert::Verlust(nachfolger) => Wert::Gewinn(entfernung(nachfolger)),
+                        Wert::Remis => Wert::Remis,
+                    },
+                    wert
+                );
+            }
+        }
+
+        assert_eq!(
+            Endspieldatenbank::from_bytes(&datenbank.to_bytes()),
+            Some(datenbank)
+        );
+    }
+}
@@ -0,0 +1,572 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use crate::brett::{Partie, Remisregeln, SpielBrett, Spieler, Zug};
+
+use super::{
+    get_datenbank_halbzuege, is_remis_in_suche, Endspieldatenbank, Eroeffnungsbuch, Gewichte,
+    Grenzen, SuchEinstellungen, SuchErgebnis, Zeitpunkt, VERZWEIGUNGSFAKTOR,
+};
+
+/// Die Einstellungen einer `Engine`
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct EngineOptionen {
+    pub max_depth: u32,
+    /// Mit einer Zeitbegrenzung wird die Tiefe schrittweise erhöht, solange die nächste Tiefe
+    /// voraussichtlich noch in die Zeit passt. Dauert sie trotzdem zu lange, wird sie abgebrochen.
+    pub max_zeit: Option<Duration>,
+    /// Auf so viele Threads werden die Züge an der Wurzel verteilt, unter WebAssembly wird
+    /// immer in einem gesucht
+    pub threads: usize,
+    /// Die Suche hält statt einer Hashtabelle den ganzen Suchbaum im Speicher. Die Hash-Größe
+    /// in MB begrenzt deshalb den Suchbaum, eine Tiefe, die nicht hineinpasst, wird abgebrochen.
+    pub hash_mb: usize,
+    pub gewichte: Gewichte,
+}
+
+impl Default for EngineOptionen {
+    fn default() -> Self {
+        Self::from(SuchEinstellungen::new(7))
+    }
+}
+
+impl From<SuchEinstellungen> for EngineOptionen {
+    fn from(einstellungen: SuchEinstellungen) -> Self {
+        Self {
+            max_depth: einstellungen.max_depth,
+            max_zeit: einstellungen.max_zeit,
+            threads: 1,
+            hash_mb: 1024,
+            gewichte: einstellungen.gewichte,
+        }
+    }
+}
+
+/// Woher der Zug einer `Engine` stammt
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Quelle {
+    Suche,
+    Eroeffnungsbuch,
+    Endspieldatenbank,
+}
+
+/// Das Ergebnis von `Engine::suche` mit Zügen statt Stellungen
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct EngineErgebnis {
+    pub zug: Zug,
+    /// Die Stellung nach `zug`
+    pub nachher: SpielBrett,
+    /// Aus Sicht des Spielers am Zug in Hundertstel Steinen
+    pub bewertung: i32,
+    /// Die erwartete Fortsetzung, beginnend mit `zug`
+    pub hauptvariante: Vec<Zug>,
+    /// 0 für Züge aus dem Eröffnungsbuch und der Endspieldatenbank
+    pub depth: u32,
+    /// Die Knoten aller bisher gesuchten Tiefen
+    pub nodes: usize,
+    pub zeit: Duration,
+    pub quelle: Quelle,
+}
+
+/// Hält eine laufende Suche von einem anderen Thread aus an. Die Suche gibt dann das Ergebnis
+/// der letzten vollständigen Tiefe zurück.
+#[derive(Clone, Default, Debug)]
+pub struct StopSignal(Arc<AtomicBool>);
+
+impl StopSignal {
+    pub fn stop(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_gestoppt(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Vor jeder neuen Suche aufrufen, nicht erst in der Suche, sonst geht ein `stop` verloren,
+    /// das kurz nach dem Start der Suche kommt.
+    pub fn zuruecksetzen(&self) {
+        self.0.store(false, Ordering::Relaxed);
+    }
+}
+
+/// Die Engine für andere Programme: eine Stellung setzen und darin den besten Zug suchen.
+/// Eröffnungsbuch und Endspieldatenbank werden geteilt, damit sich die Engine billig kopieren
+/// lässt. Kopien teilen sich auch das `StopSignal`.
+#[derive(Clone, Debug)]
+pub struct Engine {
+    optionen: EngineOptionen,
+    partie: Partie,
+    buch: Option<Arc<Eroeffnungsbuch>>,
+    datenbank: Option<Arc<Endspieldatenbank>>,
+    stop: StopSignal,
+}
+
+impl Default for Engine {
+    fn default() -> Self {
+        Self::new(EngineOptionen::default())
+    }
+}
+
+impl Engine {
+    pub fn new(optionen: EngineOptionen) -> Self {
+        Self {
+            optionen,
+            partie: Partie::new(
+                SpielBrett::startaufstellung(),
+                Spieler::Schwarz,
+                Remisregeln::default(),
+            ),
+            buch: None,
+            datenbank: None,
+            stop: StopSignal::default(),
+        }
+    }
+
+    pub fn get_optionen(&self) -> EngineOptionen {
+        self.optionen
+    }
+
+    pub fn set_optionen(&mut self, optionen: EngineOptionen) {
+        self.optionen = optionen;
+    }
+
+    pub fn set_eroeffnungsbuch(&mut self, buch: Option<Arc<Eroeffnungsbuch>>) {
+        self.buch = buch;
+    }
+
+    pub fn set_endspieldatenbank(&mut self, datenbank: Option<Arc<Endspieldatenbank>>) {
+        self.datenbank = datenbank;
+    }
+
+    /// Beginnt eine neue Partie in der Stellung, ohne frühere Stellungen für die Remisregeln
+    pub fn set_position(&mut self, brett: SpielBrett, am_zug: Spieler) {
+        let regeln = self.partie.get_regeln();
+        self.partie = Partie::new(brett, am_zug, regeln);
+    }
+
+    /// Übernimmt eine Partie, damit die Suche Wiederholungen erkennt
+    pub fn set_partie(&mut self, partie: Partie) {
+        self.partie = partie;
+    }
+
+    pub fn get_partie(&self) -> &Partie {
+        &self.partie
+    }
+
+    /// Führt einen Zug in der aktuellen Partie aus, siehe `Partie::ziehe`
+    pub fn ziehe(&mut self, nachher: SpielBrett) -> Option<()> {
+        self.partie.ziehe(nachher)
+    }
+
+    pub fn get_stop_signal(&self) -> StopSignal {
+        self.stop.clone()
+    }
+
+    /// Sucht den besten Zug mit schrittweise steigender Tiefe und ruft `fortschritt` nach jeder
+    /// vollständigen Tiefe auf. Die erste Tiefe wird immer zu Ende gesucht, auch wenn das
+    /// `StopSignal` schon vorher gesetzt war. Gibt `None` zurück, wenn der Spieler am Zug nicht
+    /// ziehen kann oder sich der gefundene Zug nicht als `Zug` darstellen lässt.
+    pub fn suche(&self, mut fortschritt: impl FnMut(&EngineErgebnis)) -> Option<EngineErgebnis> {
+        let start = Zeitpunkt::jetzt();
+        let brett = self.partie.get_brett();
+        let am_zug = self.partie.get_am_zug();
+        if brett.get_possible_zuege(am_zug).is_empty() {
+            return None;
+        }
+
+        let vorberechnet = match self
+            .buch
+            .as_deref()
+            .and_then(|buch| brett.get_buch_ergebnis(am_zug, buch, None))
+        {
+            Some(ergebnis) => Some((ergebnis, Quelle::Eroeffnungsbuch)),
+            None => self
+                .datenbank
+                .as_deref()
+                .and_then(|datenbank| {
+                    brett.get_datenbank_ergebnis(
+                        am_zug,
+                        datenbank,
+                        get_datenbank_halbzuege(&self.partie),
+                    )
+                })
+                .map(|ergebnis| (ergebnis, Quelle::Endspieldatenbank)),
+        };
+        if let Some(ergebnis) = vorberechnet.and_then(|(ergebnis, quelle)| {
+            self.to_engine_ergebnis(ergebnis, quelle, 0, start.get_vergangen())
+        }) {
+            fortschritt(&ergebnis);
+            return Some(ergebnis);
+        }
+
+        let grenzen = Grenzen {
+            stop: Some(&self.stop.0),
+            max_speicher: Some(self.optionen.hash_mb.saturating_mul(1 << 20)),
+            bis: self.optionen.max_zeit.map(|zeit| start + zeit),
+        };
+        let mut best = None;
+        let mut nodes = 0;
+        for depth in 1..=self.optionen.max_depth.max(1) {
+            let ergebnis = match depth {
+                1 => self.suche_tiefe(depth, &Grenzen::default()),
+                _ => self.suche_tiefe(depth, &grenzen),
+            };
+            let Some(ergebnis) = ergebnis else {
+                break;
+            };
+            nodes += ergebnis.nodes;
+            let letzte_zeit = ergebnis.zeit;
+            let Some(ergebnis) =
+                self.to_engine_ergebnis(ergebnis, Quelle::Suche, nodes, start.get_vergangen())
+            else {
+                break;
+            };
+            fortschritt(&ergebnis);
+            best = Some(ergebnis);
+            if self.stop.is_gestoppt() {
+                break;
+            }
+            if let Some(max_zeit) = self.optionen.max_zeit {
+                if start.get_vergangen() + letzte_zeit * VERZWEIGUNGSFAKTOR > max_zeit {
+                    break;
+                }
+            }
+        }
+        best
+    }
+
+    /// Sucht eine Tiefe, mit mehreren Threads bekommt jeder einen Teil der Züge an der Wurzel
+    fn suche_tiefe(&self, depth: u32, grenzen: &Grenzen) -> Option<SuchErgebnis> {
+        let start = Zeitpunkt::jetzt();
+        let brett = self.partie.get_brett();
+        let am_zug = self.partie.get_am_zug();
+        let zuege = brett.get_possible_zuege(am_zug);
+        // Unter WebAssembly lassen sich keine Threads starten
+        let threads = match cfg!(target_arch = "wasm32") {
+            true => 1,
+            false => self.optionen.threads.min(zuege.len()),
+        };
+        if threads <= 1 || depth <= 1 {
+            return brett.suche_begrenzt(
+                depth,
+                am_zug,
+                self.datenbank.as_deref(),
+                Some(&self.partie),
+                &self.optionen.gewichte,
+                grenzen,
+            );
+        }
+
+        let grenzen = Grenzen {
+            max_speicher: grenzen.max_speicher.map(|max| max / threads),
+            ..*grenzen
+        };
+        let kinder = thread::scope(|scope| {
+            let handles: Vec<_> = (0..threads)
+                .map(|index| {
+                    let zuege = &zuege;
+                    let grenzen = &grenzen;
+                    scope.spawn(move || {
+                        zuege
+                            .iter()
+                            .skip(index)
+                            .step_by(threads)
+                            .map(|(_, nachher)| self.suche_kind(nachher, depth, grenzen))
+                            .collect::<Option<Vec<_>>>()
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect::<Option<Vec<_>>>()
+        })?;
+
+        let nodes = 1 + kinder
+            .iter()
+            .flatten()
+            .map(|kind| kind.nodes)
+            .sum::<usize>();
+        let mut best = kinder
+            .into_iter()
+            .flatten()
+            .max_by_key(|kind| kind.bewertung)
+            .unwrap();
+        best.nodes = nodes;
+        best.zeit = start.get_vergangen();
+        Some(best)
+    }
+
+    /// Sucht die Stellung nach einem Zug an der Wurzel und bewertet sie aus Sicht des Spielers
+    /// an der Wurzel
+    fn suche_kind(
+        &self,
+        nachher: &SpielBrett,
+        depth: u32,
+        grenzen: &Grenzen,
+    ) -> Option<SuchErgebnis> {
+        let am_zug = self.partie.get_am_zug();
+        let mut partie = self.partie.clone();
+        let partie = partie.ziehe(nachher.clone()).map(|()| partie);
+        // Dieselbe Regel wie in `suche_begrenzt`, damit die Zahl der Threads nichts ändert
+        let remis = partie.as_ref().is_some_and(|nach_zug| {
+            is_remis_in_suche(
+                &self.partie,
+                nachher.get_hash(!am_zug),
+                nachher,
+                nach_zug.get_halbzuege_ohne_fortschritt(),
+                nach_zug.get_halbzuege_damen_endspiel(),
+                1,
+            )
+        });
+        let (bewertung, fortsetzung, nodes) = if remis {
+            (0, Vec::new(), 1)
+        } else {
+            let ergebnis = nachher.suche_begrenzt(
+                depth - 1,
+                !am_zug,
+                self.datenbank.as_deref(),
+                partie.as_ref(),
+                &self.optionen.gewichte,
+                grenzen,
+            )?;
+            (
+                ergebnis.bewertung.saturating_neg(),
+                ergebnis.hauptvariante,
+                ergebnis.nodes,
+            )
+        };
+        let mut hauptvariante = vec![nachher.clone()];
+        hauptvariante.extend(fortsetzung);
+        Some(SuchErgebnis {
+            best_move: nachher.clone(),
+            bewertung,
+            hauptvariante,
+            depth,
+            nodes,
+            zeit: Duration::ZERO,
+        })
+    }
+
+    /// Gibt `None` zurück, wenn sich der gefundene Zug nicht als `Zug` darstellen lässt
+    fn to_engine_ergebnis(
+        &self,
+        ergebnis: SuchErgebnis,
+        quelle: Quelle,
+        nodes: usize,
+        zeit: Duration,
+    ) -> Option<EngineErgebnis> {
+        let mut brett = self.partie.get_brett();
+        let mut spieler = self.partie.get_am_zug();
+        let mut hauptvariante = Vec::new();
+        for nachher in &ergebnis.hauptvariante {
+            let Some(zug) = brett.get_zug(nachher, spieler) else {
+                break;
+            };
+            hauptvariante.push(zug);
+            brett = nachher;
+            spieler = !spieler;
+        }
+        Some(EngineErgebnis {
+            zug: hauptvariante.first()?.clone(),
+            nachher: ergebnis.best_move,
+            bewertung: ergebnis.bewertung,
+            hauptvariante,
+            depth: ergebnis.depth,
+            nodes,
+            zeit,
+            quelle,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_engine(optionen: EngineOptionen) -> Engine {
+        let mut engine = Engine::new(optionen);
+        let brett = SpielBrett::parse(concat!(
+            "_ _ _ _ \n",
+            " _ _ _ _\n",
+            "_ _ m _ \n",
+            " _ _ _ _\n",
+            "_ _ _ _ \n",
+            " _ _ _ _\n",
+            "_ _ _ m \n",
+            " _ _ _ c",
+        ))
+        .unwrap();
+        engine.set_position(brett, Spieler::Weiss);
+        engine
+    }
+
+    #[test]
+    fn test_engine() {
+        let optionen = EngineOptionen {
+            max_depth: 4,
+            ..EngineOptionen::default()
+        };
+        let engine = get_engine(optionen);
+        let mut tiefen = Vec::new();
+        let ergebnis = engine
+            .suche(|ergebnis| tiefen.push(ergebnis.depth))
+            .unwrap();
+        assert_eq!(tiefen, vec![1, 2, 3, 4]);
+        assert_eq!(ergebnis.zug.to_notation(), "32x23");
+        assert_eq!(ergebnis.hauptvariante[0], ergebnis.zug);
+        assert_eq!(ergebnis.quelle, Quelle::Suche);
+
+        // Mehrere Threads finden dieselbe Bewertung
+        let parallel = get_engine(EngineOptionen {
+            threads: 4,
+            ..optionen
+        })
+        .suche(|_| {})
+        .unwrap();
+        assert_eq!(parallel.bewertung, ergebnis.bewertung);
+        assert_eq!(parallel.zug, ergebnis.zug);
+
+        let mut engine = Engine::default();
+        engine.set_position(SpielBrett::default(), Spieler::Schwarz);
+        assert_eq!(engine.suche(|_| {}), None);
+
+        // Ein Brett, das kein erlaubter Zug erreicht, ergibt kein Ergebnis statt eines Panics
+        let ergebnis = SuchErgebnis {
+            best_move: SpielBrett::default(),
+            bewertung: 0,
+            hauptvariante: vec![SpielBrett::default()],
+            depth: 1,
+            nodes: 1,
+            zeit: Duration::ZERO,
+        };
+        let engine = get_engine(optionen);
+        assert_eq!(
+            engine.to_engine_ergebnis(ergebnis, Quelle::Suche, 1, Duration::ZERO),
+            None
+        );
+    }
+
+    #[test]
+    fn test_engine_remis() {
+        let brett = |text: &str| SpielBrett::parse(text).unwrap();
+        let mut partie = Partie::new(
+            brett(concat!(
+                "_ _ _ M \n",
+                " _ _ _ _\n",
+                "_ _ _ _ \n",
+                " _ _ _ _\n",
+                "_ _ _ _ \n",
+                " _ _ _ _\n",
+                "_ _ _ _ \n",
+                " C _ _ C",
+            )),
+            Spieler::Weiss,
+            Remisregeln {
+                damen_endspiel: false,
+                ..Remisregeln::default()
+            },
+        );
+        partie
+            .ziehe(brett(concat!(
+                "_ _ _ M \n",
+                " _ _ _ _\n",
+                "_ _ _ _ \n",
+                " _ _ _ _\n",
+                "_ _ _ _ \n",
+                " _ _ _ _\n",
+                "_ _ _ C \n",
+                " C _ _ _",
+            )))
+            .unwrap();
+        partie
+            .ziehe(brett(concat!(
+                "_ _ _ _ \n",
+                " _ _ _ M\n",
+                "_ _ _ _ \n",
+                " _ _ _ _\n",
+                "_ _ _ _ \n",
+                " _ _ _ _\n",
+                "_ _ _ C \n",
+                " C _ _ _",
+            )))
+            .unwrap();
+        partie
+            .ziehe(brett(concat!(
+                "_ _ _ _ \n",
+                " _ _ _ M\n",
+                "_ _ _ _ \n",
+                " _ _ _ _\n",
+                "_ _ _ _ \n",
+                " _ _ _ _\n",
+                "_ _ _ _ \n",
+                " C _ _ C",
+            )))
+            .unwrap();
+
+        // Schwarz rettet sich mit der ersten Wiederholung, egal mit wie vielen Threads gesucht wird
+        for threads in [1, 4] {
+            let mut engine = Engine::new(EngineOptionen {
+                max_depth: 3,
+                threads,
+                ..EngineOptionen::default()
+            });
+            engine.set_partie(partie.clone());
+            let ergebnis = engine.suche(|_| {}).unwrap();
+            assert_eq!(ergebnis.bewertung, 0);
+            assert_eq!(ergebnis.zug.to_notation(), "8-4");
+        }
+    }
+
+    #[test]
+    fn test_engine_grenzen() {
+        let engine = Engine::new(EngineOptionen {
+            max_depth: 30,
+            ..EngineOptionen::default()
+        });
+        let stop = engine.get_stop_signal();
+        let ergebnis = engine
+            .suche(|ergebnis| {
+                if ergebnis.depth == 2 {
+                    stop.stop();
+                }
+            })
+            .unwrap();
+        assert_eq!(ergebnis.depth, 2);
+
+        // Ein Stop vor dem Start der Suche bleibt bis zum Zurücksetzen gültig
+        assert_eq!(engine.suche(|_| {}).unwrap().depth, 1);
+        let engine = Engine::new(EngineOptionen {
+            max_depth: 3,
+            ..EngineOptionen::default()
+        });
+        let stop = engine.get_stop_signal();
+        stop.stop();
+        assert_eq!(engine.suche(|_| {}).unwrap().depth, 1);
+        stop.zuruecksetzen();
+        assert_eq!(engine.suche(|_| {}).unwrap().depth, 3);
+
+        // Ohne Speicher für den Suchbaum bleibt nur die erste Tiefe
+        let engine = Engine::new(EngineOptionen {
+            hash_mb: 0,
+            ..EngineOptionen::default()
+        });
+        assert_eq!(engine.suche(|_| {}).unwrap().depth, 1);
+
+        let engine = Engine::new(EngineOptionen {
+            max_depth: 30,
+            max_zeit: Some(Duration::from_millis(50)),
+            threads: 2,
+            ..EngineOptionen::default()
+        });
+        let start = Zeitpunkt::jetzt();
+        assert!(engine.suche(|_| {}).is_some());
+        assert!(start.get_vergangen() < Duration::from_secs(2));
+    }
+}
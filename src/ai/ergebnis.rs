@@ -0,0 +1,43 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    time::Duration,
+};
+
+use crate::brett::SpielBrett;
+
+/// Das Ergebnis einer Suche. Die Bewertung ist aus Sicht des Spielers am Zug.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SuchErgebnis {
+    pub best_move: SpielBrett,
+    pub bewertung: i32,
+    /// Die erwartete Fortsetzung, beginnend mit `best_move`
+    pub hauptvariante: Vec<SpielBrett>,
+    pub depth: u32,
+    pub nodes: usize,
+    pub zeit: Duration,
+}
+
+impl SuchErgebnis {
+    pub fn nodes_per_second(&self) -> f64 {
+        let sekunden = self.zeit.as_secs_f64();
+        if sekunden == 0.0 {
+            return 0.0;
+        }
+        self.nodes as f64 / sekunden
+    }
+}
+
+impl Display for SuchErgebnis {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Bewertung {}, Tiefe {}, {} Knoten in {:.2?} ({:.0} Knoten/s), Hauptvariante {} Züge",
+            self.bewertung,
+            self.depth,
+            self.nodes,
+            self.zeit,
+            self.nodes_per_second(),
+            self.hauptvariante.len(),
+        )
+    }
+}
@@ -0,0 +1,195 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use crate::{
+    brett::{SpielBrett, Spieler},
+    pdn::{Ergebnis, PdnSpiel},
+};
+
+use super::Zufall;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BuchZug {
+    pub notation: String,
+    pub gewicht: u32,
+}
+
+/// Ordnet Stellungen (über `SpielBrett::get_hash`) gewichtete Züge zu.
+///
+/// Dateiformat: eine Zeile pro Zug mit Hash in Hexadezimal, Zug in Notation und Gewicht,
+/// zum Beispiel `0f3a9c21d8e4b705 11-15 12`. Zeilen, die mit `#` beginnen, werden ignoriert.
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct Eroeffnungsbuch {
+    eintraege: HashMap<u64, Vec<BuchZug>>,
+}
+
+impl Eroeffnungsbuch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.eintraege.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.eintraege.is_empty()
+    }
+
+    pub fn add(&mut self, hash: u64, notation: &str, gewicht: u32) {
+        let zuege = self.eintraege.entry(hash).or_default();
+        match zuege.iter_mut().find(|zug| zug.notation == notation) {
+            Some(zug) => zug.gewicht += gewicht,
+            None => zuege.push(BuchZug {
+                notation: notation.to_string(),
+                gewicht,
+            }),
+        }
+    }
+
+    pub fn get_zuege(&self, brett: &SpielBrett, am_zug: Spieler) -> &[BuchZug] {
+        match self.eintraege.get(&brett.get_hash(am_zug)) {
+            Some(zuege) => zuege,
+            None => &[],
+        }
+    }
+
+    /// Wählt einen Buchzug aus. Ohne `zufall` wird immer der Zug mit dem höchsten Gewicht
+    /// gespielt, sonst wird zufällig entsprechend der Gewichte ausgewählt.
+    pub fn get_zug(
+        &self,
+        brett: &SpielBrett,
+        am_zug: Spieler,
+        zufall: Option<&mut Zufall>,
+    ) -> Option<SpielBrett> {
+        // Züge, die in dieser Stellung nicht erlaubt sind (z.B. durch Hash-Kollisionen),
+        // werden ignoriert
+        let zuege: Vec<(u32, SpielBrett)> = self
+            .get_zuege(brett, am_zug)
+            .iter()
+            .filter(|zug| zug.gewicht > 0)
+            .filter_map(|zug| Some((zug.gewicht, brett.find_zug(am_zug, &zug.notation)?)))
+            .collect();
+
+        match zufall {
+            None => zuege
+                .into_iter()
+                .max_by_key(|(gewicht, _)| *gewicht)
+                .map(|(_, brett)| brett),
+            Some(zufall) => {
+                let summe: u64 = zuege.iter().map(|(gewicht, _)| *gewicht as u64).sum();
+                let mut auswahl = zufall.next_bis(summe);
+                for (gewicht, brett) in zuege {
+                    if auswahl < gewicht as u64 {
+                        return Some(brett);
+                    }
+                    auswahl -= gewicht as u64;
+                }
+                None
+            }
+        }
+    }
+
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut buch = Self::new();
+        for zeile in text.lines() {
+            let zeile = zeile.trim();
+            if zeile.is_empty() || zeile.starts_with('#') {
+                continue;
+            }
+            let mut teile = zeile.split_whitespace();
+            let hash = u64::from_str_radix(teile.next()?, 16).ok()?;
+            let notation = teile.next()?;
+            let gewicht = teile.next()?.parse().ok()?;
+            if teile.next().is_some() {
+                return None;
+            }
+            buch.add(hash, notation, gewicht);
+        }
+        Some(buch)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Option<Self> {
+        Self::parse(&fs::read_to_string(path).ok()?)
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut hashes: Vec<_> = self.eintraege.keys().copied().collect();
+        hashes.sort();
+
+        let mut result = String::from("# Dame Eröffnungsbuch: Hash Zug Gewicht\n");
+        for hash in hashes {
+            for zug in &self.eintraege[&hash] {
+                result.push_str(&format!("{hash:016x} {} {}\n", zug.notation, zug.gewicht));
+            }
+        }
+        result
+    }
+
+    /// Erstellt ein Buch aus den ersten `max_halbzuege` Zügen jedes Spiels. Züge der
+    /// Verliererseite werden nicht aufgenommen.
+    pub fn from_spiele(spiele: &[PdnSpiel], max_halbzuege: usize) -> Self {
+        let mut buch = Self::new();
+        for spiel in spiele {
+            for (vorher, am_zug, nachher) in spiel.get_zuege().into_iter().take(max_halbzuege) {
+                if spiel.ergebnis == Ergebnis::Sieg(!am_zug) {
+                    continue;
+                }
+                let Some(zug) = vorher.get_zug(nachher, am_zug) else {
+                    break;
+                };
+                buch.add(vorher.get_hash(am_zug), &zug.to_notation(), 1);
+            }
+        }
+        buch
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eroeffnungsbuch() {
+        let spiele = PdnSpiel::parse_alle(concat!(
+            "1. 10-14 23-19 2-0\n",
+            "[Event \"2\"]\n",
+            "1. 10-14 23-19 *\n",
+            "[Event \"3\"]\n",
+            "1. 9-13 23-19 0-2\n",
+        ))
+        .unwrap();
+        let buch = Eroeffnungsbuch::from_spiele(&spiele, 10);
+
+        let start = SpielBrett::startaufstellung();
+        assert_eq!(
+            buch.get_zuege(&start, Spieler::Schwarz),
+            &[
+                BuchZug {
+                    notation: "10-14".to_string(),
+                    gewicht: 1
+                },
+                BuchZug {
+                    notation: "9-13".to_string(),
+                    gewicht: 1
+                }
+            ]
+        );
+        assert_eq!(buch.get_zuege(&start, Spieler::Weiss), &[]);
+
+        let nach_10_14 = start.find_zug(Spieler::Schwarz, "10-14").unwrap();
+        assert_eq!(
+            buch.get_zug(&nach_10_14, Spieler::Weiss, None),
+            nach_10_14.find_zug(Spieler::Weiss, "23-19")
+        );
+
+        let geladen = Eroeffnungsbuch::parse(&buch.to_text()).unwrap();
+        assert_eq!(geladen, buch);
+
+        let mut zufall = Zufall::new(42);
+        for _ in 0..10 {
+            assert!(buch
+                .get_zug(&start, Spieler::Schwarz, Some(&mut zufall))
+                .is_some());
+        }
+    }
+}
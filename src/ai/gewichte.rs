@@ -0,0 +1,185 @@
+use std::{fs, path::Path};
+
+use crate::brett::{Feld, Position, SpielBrett, Spieler};
+
+/// Die Gewichte der Bewertungsfunktion in Hundertstel Steinen.
+///
+/// Dateiformat: eine Zeile pro Gewicht mit Name und Wert, zum Beispiel `dame 300`. Fehlende
+/// Gewichte behalten ihren Standardwert, Zeilen, die mit `#` beginnen, werden ignoriert.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Gewichte {
+    pub stein: i32,
+    pub dame: i32,
+    /// Pro Zeile, die ein Stein von der eigenen Grundreihe entfernt ist
+    pub vorgerueckt: i32,
+    /// Pro Stein auf der eigenen Grundreihe, der dort gegnerische Damen verhindert
+    pub grundreihe: i32,
+    /// Pro Figur auf einem der acht Felder in der Mitte
+    pub zentrum: i32,
+}
+
+impl Default for Gewichte {
+    fn default() -> Self {
+        Self {
+            stein: 100,
+            dame: 300,
+            vorgerueckt: 0,
+            grundreihe: 0,
+            zentrum: 0,
+        }
+    }
+}
+
+impl Gewichte {
+    pub const NAMEN: [&'static str; 5] = ["stein", "dame", "vorgerueckt", "grundreihe", "zentrum"];
+
+    pub fn to_array(self) -> [i32; 5] {
+        [
+            self.stein,
+            self.dame,
+            self.vorgerueckt,
+            self.grundreihe,
+            self.zentrum,
+        ]
+    }
+
+    pub fn from_array([stein, dame, vorgerueckt, grundreihe, zentrum]: [i32; 5]) -> Self {
+        Self {
+            stein,
+            dame,
+            vorgerueckt,
+            grundreihe,
+            zentrum,
+        }
+    }
+
+    /// Setzt ein Gewicht über seinen Namen aus dem Dateiformat
+    pub fn set(&mut self, name: &str, wert: i32) -> Option<()> {
+        let index = Self::NAMEN.iter().position(|&gewicht| gewicht == name)?;
+        let mut werte = self.to_array();
+        werte[index] = wert;
+        *self = Self::from_array(werte);
+        Some(())
+    }
+
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut gewichte = Self::default();
+        for zeile in text.lines() {
+            let zeile = zeile.trim();
+            if zeile.is_empty() || zeile.starts_with('#') {
+                continue;
+            }
+            let mut teile = zeile.split_whitespace();
+            let name = teile.next()?;
+            let wert = teile.next()?.parse().ok()?;
+            if teile.next().is_some() {
+                return None;
+            }
+            gewichte.set(name, wert)?;
+        }
+        Some(gewichte)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Option<Self> {
+        Self::parse(&fs::read_to_string(path).ok()?)
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut result = String::from("# Dame Bewertung: Name Wert\n");
+        for (name, wert) in Self::NAMEN.iter().zip(self.to_array()) {
+            result.push_str(&format!("{name} {wert}\n"));
+        }
+        result
+    }
+
+    /// Die Merkmale, mit denen die Gewichte multipliziert werden, jeweils als Differenz
+    /// zwischen `perspektive` und dem Gegner
+    pub fn get_merkmale(brett: &SpielBrett, perspektive: Spieler) -> [i32; 5] {
+        let mut merkmale = [0; 5];
+        for zeile in 0..SpielBrett::SIZE {
+            for spalte in 0..SpielBrett::SIZE {
+                let position = Position { spalte, zeile };
+                if !position.valid() {
+                    continue;
+                }
+                let (spieler, dame) = match brett.get(position) {
+                    Feld::Leer => continue,
+                    Feld::Stein(spieler) => (spieler, false),
+                    Feld::Dame(spieler) => (spieler, true),
+                };
+                let vorzeichen = if spieler == perspektive { 1 } else { -1 };
+
+                let grundreihe = (!spieler).dame_zeile();
+                let mut figur = [0; 5];
+                if dame {
+                    figur[1] = 1;
+                } else {
+                    figur[0] = 1;
+                    figur[2] = zeile.abs_diff(grundreihe) as i32;
+                    figur[3] = i32::from(zeile == grundreihe);
+                }
+                figur[4] = i32::from((3..=4).contains(&zeile) && (2..=5).contains(&spalte));
+
+                for (merkmal, wert) in merkmale.iter_mut().zip(figur) {
+                    *merkmal += vorzeichen * wert;
+                }
+            }
+        }
+        merkmale
+    }
+
+    /// Bewertet das Brett aus Sicht von `perspektive`
+    pub fn bewerte(&self, brett: &SpielBrett, perspektive: Spieler) -> i32 {
+        let statistik = brett.get_statistik();
+        // Wer keine Figuren mehr hat, hat verloren
+        if statistik.schwarz.steine + statistik.schwarz.damen == 0
+            || statistik.weiss.steine + statistik.weiss.damen == 0
+        {
+            return statistik.to_number_mit(perspektive, self);
+        }
+        Self::get_merkmale(brett, perspektive)
+            .into_iter()
+            .zip(self.to_array())
+            .map(|(merkmal, gewicht)| merkmal * gewicht)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gewichte() {
+        let brett = SpielBrett::parse(concat!(
+            "_ _ _ M \n",
+            " _ _ _ _\n",
+            "_ _ m _ \n",
+            " _ _ _ _\n",
+            "_ _ _ _ \n",
+            " _ _ _ _\n",
+            "_ _ _ _ \n",
+            " C _ c _",
+        ))
+        .unwrap();
+        assert_eq!(
+            Gewichte::get_merkmale(&brett, Spieler::Schwarz),
+            [0, 0, 2, -1, 0]
+        );
+
+        let gewichte = Gewichte::default();
+        assert_eq!(gewichte.bewerte(&brett, Spieler::Schwarz), 0);
+        assert_eq!(
+            gewichte.bewerte(&brett, Spieler::Schwarz),
+            brett
+                .get_statistik()
+                .to_number_mit(Spieler::Schwarz, &gewichte)
+        );
+
+        let gewichte = Gewichte::parse("# Test\nvorgerueckt 5\ngrundreihe 20\n").unwrap();
+        assert_eq!(gewichte.stein, 100);
+        assert_eq!(gewichte.bewerte(&brett, Spieler::Weiss), 10);
+        assert_eq!(Gewichte::parse(&gewichte.to_text()), Some(gewichte));
+        assert_eq!(Gewichte::parse("turm 5"), None);
+    }
+}
@@ -1,11 +1,295 @@
-use std::collections::HashSet;
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
 
-use crate::brett::{SpielBrett, Spieler};
+use crate::brett::{
+    is_damen_endspiel_remis, is_unumkehrbar, zaehle_damen_endspiel, Partie, SpielBrett, Spieler,
+};
 
+mod endspieldatenbank;
+pub use endspieldatenbank::*;
+mod ergebnis;
+pub use ergebnis::*;
+mod analyse;
+pub use analyse::*;
+mod engine;
+pub use engine::*;
+mod eroeffnungsbuch;
+pub use eroeffnungsbuch::*;
+mod gewichte;
+pub use gewichte::*;
 mod statistik;
+pub mod tuning;
+mod zeitpunkt;
+pub use zeitpunkt::*;
+mod zufall;
+pub use zufall::*;
+
+/// Einstellungen einer Engine, damit sich verschiedene Engines miteinander vergleichen lassen
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct SuchEinstellungen {
+    pub max_depth: u32,
+    /// Mit einer Zeitbegrenzung wird die Tiefe schrittweise bis `max_depth` erhöht, solange
+    /// die nächste Tiefe voraussichtlich noch in die Zeit passt
+    pub max_zeit: Option<Duration>,
+    pub gewichte: Gewichte,
+}
+
+impl SuchEinstellungen {
+    pub fn new(max_depth: u32) -> Self {
+        Self {
+            max_depth,
+            max_zeit: None,
+            gewichte: Gewichte::default(),
+        }
+    }
+
+    /// Liest eine Engine aus einer Liste wie `tiefe=5,zeit=500,gewichte=datei.txt,dame=350`.
+    /// Die Zeit ist in Millisekunden pro Zug, einzelne Gewichte überschreiben die aus der Datei.
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut einstellungen = Self::new(5);
+        let mut tiefe = None;
+        let mut einzelne_gewichte = Vec::new();
+        for teil in text.split(',') {
+            let (name, wert) = teil.split_once('=')?;
+            match name.trim() {
+                "tiefe" => tiefe = Some(wert.parse().ok()?),
+                "zeit" => einstellungen.max_zeit = Some(Duration::from_millis(wert.parse().ok()?)),
+                "gewichte" => einstellungen.gewichte = Gewichte::load(wert.trim())?,
+                name => einzelne_gewichte.push((name, wert.parse().ok()?)),
+            }
+        }
+        for (name, wert) in einzelne_gewichte {
+            einstellungen.gewichte.set(name, wert)?;
+        }
+        // Mit Zeitbegrenzung und ohne Tiefe bestimmt nur die Zeit, wie tief gesucht wird
+        einstellungen.max_depth = match (tiefe, einstellungen.max_zeit) {
+            (Some(tiefe), _) => tiefe,
+            (None, Some(_)) => u32::MAX,
+            (None, None) => einstellungen.max_depth,
+        };
+        Some(einstellungen)
+    }
+}
+
+/// Wann eine laufende Suche abgebrochen wird
+#[derive(Clone, Copy, Default, Debug)]
+pub(crate) struct Grenzen<'a> {
+    pub stop: Option<&'a AtomicBool>,
+    /// Wie viele Bytes der Suchbaum höchstens belegen darf
+    pub max_speicher: Option<usize>,
+    pub bis: Option<Zeitpunkt>,
+}
+
+impl Grenzen<'_> {
+    fn is_erreicht(&self, speicher: usize) -> bool {
+        self.stop.is_some_and(|stop| stop.load(Ordering::Relaxed))
+            || self.max_speicher.is_some_and(|max| speicher > max)
+            || self.bis.is_some_and(|bis| Zeitpunkt::jetzt() >= bis)
+    }
+}
+
+/// Ob eine Stellung `depth` Halbzüge nach der aktuellen Stellung der `partie` in der Suche als
+/// Remis zählt. Anders als in der Partie reicht schon eine Wiederholung, weil sie sich beliebig
+/// oft fortsetzen lässt.
+pub(crate) fn is_remis_in_suche(
+    partie: &Partie,
+    hash: u64,
+    brett: &SpielBrett,
+    ohne_fortschritt: u32,
+    damen_endspiel: u32,
+    depth: u32,
+) -> bool {
+    let regeln = partie.get_regeln();
+    if let Some(max_zuege) = regeln.max_zuege_ohne_fortschritt {
+        if ohne_fortschritt >= max_zuege * 2 {
+            return true;
+        }
+    }
+    if regeln.damen_endspiel && is_damen_endspiel_remis(brett, damen_endspiel) {
+        return true;
+    }
+    // Nach einem unumkehrbaren Zug kann keine Stellung der Partie mehr vorkommen
+    ohne_fortschritt >= depth && partie.get_verlauf().contains(&hash)
+}
+
+/// Die Halbzüge im Endspiel mit einer einzelnen Dame, mit denen Werte aus der
+/// Endspieldatenbank gelesen werden. Ohne die Regel zählen sie nicht.
+pub(crate) fn get_datenbank_halbzuege(partie: &Partie) -> u32 {
+    match partie.get_regeln().damen_endspiel {
+        true => partie.get_halbzuege_damen_endspiel(),
+        false => 0,
+    }
+}
+
+/// Um diesen Faktor wächst die Suchzeit ungefähr mit jeder weiteren Tiefe
+const VERZWEIGUNGSFAKTOR: u32 = 6;
+/// So viele Bytes darf der Suchbaum einer Suche mit Zeitbegrenzung höchstens belegen
+const MAX_SPEICHER: usize = 1 << 30;
 
 impl SpielBrett {
-    pub fn get_best_move(&self, max_depth: u32) -> SpielBrett {
+    /// Sucht den besten Zug für `am_zug`. Spielt einen Zug aus dem Eröffnungsbuch, falls die
+    /// Stellung darin vorkommt, und spielt Endspiele aus der Datenbank perfekt. Sonst wird wie
+    /// bei `get_best_move` gesucht, wobei Stellungen aus der Datenbank mit ihrem exakten Wert
+    /// bewertet werden.
+    pub fn get_best_move_with(
+        &self,
+        am_zug: Spieler,
+        max_depth: u32,
+        buch: Option<&Eroeffnungsbuch>,
+        datenbank: Option<&Endspieldatenbank>,
+        zufall: Option<&mut Zufall>,
+    ) -> SuchErgebnis {
+        let einstellungen = SuchEinstellungen::new(max_depth);
+        self.get_best_move_in_partie(am_zug, &einstellungen, buch, datenbank, zufall, None)
+    }
+
+    fn get_best_move_in_partie(
+        &self,
+        am_zug: Spieler,
+        einstellungen: &SuchEinstellungen,
+        buch: Option<&Eroeffnungsbuch>,
+        datenbank: Option<&Endspieldatenbank>,
+        zufall: Option<&mut Zufall>,
+        partie: Option<&Partie>,
+    ) -> SuchErgebnis {
+        if let Some(ergebnis) = buch.and_then(|buch| self.get_buch_ergebnis(am_zug, buch, zufall)) {
+            return ergebnis;
+        }
+        let halbzuege = partie.map_or(0, get_datenbank_halbzuege);
+        if let Some(ergebnis) = datenbank
+            .and_then(|datenbank| self.get_datenbank_ergebnis(am_zug, datenbank, halbzuege))
+        {
+            return ergebnis;
+        }
+        self.suche_iterativ(am_zug, einstellungen, datenbank, partie)
+    }
+
+    /// Der Zug aus dem Eröffnungsbuch als Ergebnis mit Tiefe 0
+    pub(crate) fn get_buch_ergebnis(
+        &self,
+        am_zug: Spieler,
+        buch: &Eroeffnungsbuch,
+        zufall: Option<&mut Zufall>,
+    ) -> Option<SuchErgebnis> {
+        let start = Zeitpunkt::jetzt();
+        let brett = buch.get_zug(self, am_zug, zufall)?;
+        Some(SuchErgebnis {
+            best_move: brett.clone(),
+            bewertung: 0,
+            hauptvariante: vec![brett],
+            depth: 0,
+            nodes: 0,
+            zeit: start.get_vergangen(),
+        })
+    }
+
+    /// Der perfekte Zug aus der Endspieldatenbank als Ergebnis mit Tiefe 0, siehe
+    /// `Endspieldatenbank::get_best_move`
+    pub(crate) fn get_datenbank_ergebnis(
+        &self,
+        am_zug: Spieler,
+        datenbank: &Endspieldatenbank,
+        halbzuege: u32,
+    ) -> Option<SuchErgebnis> {
+        let start = Zeitpunkt::jetzt();
+        let (brett, wert) = datenbank.get_best_move(self, am_zug, halbzuege)?;
+        Some(SuchErgebnis {
+            best_move: brett.clone(),
+            bewertung: wert.to_bewertung(),
+            hauptvariante: vec![brett],
+            depth: 0,
+            nodes: 0,
+            zeit: start.get_vergangen(),
+        })
+    }
+
+    fn suche_iterativ(
+        &self,
+        am_zug: Spieler,
+        einstellungen: &SuchEinstellungen,
+        datenbank: Option<&Endspieldatenbank>,
+        partie: Option<&Partie>,
+    ) -> SuchErgebnis {
+        let gewichte = &einstellungen.gewichte;
+        let Some(max_zeit) = einstellungen.max_zeit else {
+            return self.suche_mit(einstellungen.max_depth, am_zug, datenbank, partie, gewichte);
+        };
+
+        // Die erste Tiefe wird immer zu Ende gesucht, danach gilt das Ergebnis der letzten
+        // Tiefe, die innerhalb von Zeit und Speicher fertig geworden ist
+        let start = Zeitpunkt::jetzt();
+        let grenzen = Grenzen {
+            stop: None,
+            max_speicher: Some(MAX_SPEICHER),
+            bis: Some(start + max_zeit),
+        };
+        let mut ergebnis = self.suche_mit(1, am_zug, datenbank, partie, gewichte);
+        let mut nodes = ergebnis.nodes;
+        for depth in 2..=einstellungen.max_depth {
+            if start.get_vergangen() + ergebnis.zeit * VERZWEIGUNGSFAKTOR > max_zeit {
+                break;
+            }
+            let Some(tiefer) =
+                self.suche_begrenzt(depth, am_zug, datenbank, partie, gewichte, &grenzen)
+            else {
+                break;
+            };
+            nodes += tiefer.nodes;
+            // Wenn alle Varianten vorher enden, ändert eine größere Tiefe nichts mehr
+            let fertig = tiefer.nodes == ergebnis.nodes;
+            ergebnis = tiefer;
+            if fertig {
+                break;
+            }
+        }
+        ergebnis.nodes = nodes;
+        ergebnis.zeit = start.get_vergangen();
+        ergebnis
+    }
+
+    pub fn get_best_move(&self, am_zug: Spieler, max_depth: u32) -> SpielBrett {
+        self.suche(am_zug, max_depth).best_move
+    }
+
+    pub fn suche(&self, am_zug: Spieler, max_depth: u32) -> SuchErgebnis {
+        self.suche_mit(max_depth, am_zug, None, None, &Gewichte::default())
+    }
+
+    /// Mit einer `partie` werden Wiederholungen und zu viele Züge ohne Fortschritt als Remis
+    /// bewertet.
+    fn suche_mit(
+        &self,
+        max_depth: u32,
+        am_zug: Spieler,
+        datenbank: Option<&Endspieldatenbank>,
+        partie: Option<&Partie>,
+        gewichte: &Gewichte,
+    ) -> SuchErgebnis {
+        self.suche_begrenzt(
+            max_depth,
+            am_zug,
+            datenbank,
+            partie,
+            gewichte,
+            &Grenzen::default(),
+        )
+        .unwrap()
+    }
+
+    /// Wie `suche_mit`, gibt aber `None` zurück, wenn die Suche an eine der `grenzen` stößt
+    pub(crate) fn suche_begrenzt(
+        &self,
+        max_depth: u32,
+        am_zug: Spieler,
+        datenbank: Option<&Endspieldatenbank>,
+        partie: Option<&Partie>,
+        gewichte: &Gewichte,
+        grenzen: &Grenzen,
+    ) -> Option<SuchErgebnis> {
+        let start = Zeitpunkt::jetzt();
+        let max_depth = max_depth.max(1);
         type NodeIndex = usize;
 
         #[derive(Debug)]
@@ -13,6 +297,10 @@ impl SpielBrett {
             depth: u32,
             brett: SpielBrett,
             am_zug: Spieler,
+            parent: Option<NodeIndex>,
+            hash: u64,
+            ohne_fortschritt: u32,
+            damen_endspiel: u32,
             bewertung: Option<i32>,
             best_child: Option<NodeIndex>,
             children: Vec<NodeIndex>,
@@ -23,39 +311,92 @@ impl SpielBrett {
             bewertung: None,
             depth: 0,
             brett: self.clone(),
-            am_zug: Spieler::Computer,
+            am_zug,
+            parent: None,
+            hash: self.get_hash(am_zug),
+            ohne_fortschritt: partie.map_or(0, |partie| partie.get_halbzuege_ohne_fortschritt()),
+            damen_endspiel: partie.map_or(0, |partie| partie.get_halbzuege_damen_endspiel()),
             children: Vec::new(),
             best_child: None,
         });
 
+        let is_remis = |nodes: &[Node], parent: NodeIndex, child: &Node| {
+            let Some(partie) = partie else {
+                return false;
+            };
+            if is_remis_in_suche(
+                partie,
+                child.hash,
+                &child.brett,
+                child.ohne_fortschritt,
+                child.damen_endspiel,
+                child.depth,
+            ) {
+                return true;
+            }
+            let mut vorfahre = Some(parent);
+            while let Some(index) = vorfahre {
+                if nodes[index].hash == child.hash {
+                    return true;
+                }
+                vorfahre = nodes[index].parent;
+            }
+            false
+        };
+
         // Mögliche Züge generieren
         for depth in 1..=max_depth {
             for node_index in 0..nodes.len() {
                 let node = &nodes[node_index];
                 let node_am_zug = node.am_zug;
-                if node.depth != depth-1 {
+                if node.depth != depth - 1 || node.bewertung.is_some() {
                     continue;
                 }
+                if grenzen.is_erreicht(nodes.len() * std::mem::size_of::<Node>()) {
+                    return None;
+                }
 
-                let possible_moves = match node.brett.get_possible_moves(node.am_zug) {
-                    possible_moves if !possible_moves.is_empty() => possible_moves,
-                    _ => {
-                        let mut possible_moves = HashSet::new();
-                        possible_moves.insert(node.brett.clone());
-                        possible_moves
-                    },
-                };
+                // Wer nicht mehr ziehen kann, hat verloren, genau wie ohne Figuren
+                let possible_moves = node.brett.get_possible_moves(node.am_zug);
+                if possible_moves.is_empty() {
+                    nodes[node_index].bewertung = Some(if node_am_zug == am_zug {
+                        i32::MIN
+                    } else {
+                        i32::MAX
+                    });
+                    continue;
+                }
 
                 for child_brett in possible_moves {
                     let child_index = nodes.len();
-                    nodes.push(Node {
+                    let node = &nodes[node_index];
+                    let ohne_fortschritt = if is_unumkehrbar(&node.brett, &child_brett) {
+                        0
+                    } else {
+                        node.ohne_fortschritt + 1
+                    };
+                    let hash = match partie {
+                        Some(_) => child_brett.get_hash(!node_am_zug),
+                        None => 0,
+                    };
+                    let damen_endspiel =
+                        zaehle_damen_endspiel(&node.brett, &child_brett, node.damen_endspiel);
+                    let mut child = Node {
                         depth,
                         am_zug: !node_am_zug,
+                        parent: Some(node_index),
+                        hash,
+                        ohne_fortschritt,
+                        damen_endspiel,
                         bewertung: None,
                         brett: child_brett,
                         best_child: None,
                         children: Vec::new(),
-                    });
+                    };
+                    if is_remis(&nodes, node_index, &child) {
+                        child.bewertung = Some(0);
+                    }
+                    nodes.push(child);
                     let node = &mut nodes[node_index];
                     node.children.push(child_index);
                 }
@@ -63,19 +404,30 @@ impl SpielBrett {
         }
 
         // Bewertungen der untersten Zeile berechnen
+        let damen_endspiel_regel = partie.is_some_and(|partie| partie.get_regeln().damen_endspiel);
         for node in nodes.iter_mut() {
-            if node.depth != max_depth {
+            if node.depth != max_depth || node.bewertung.is_some() {
                 continue;
             }
 
-            node.bewertung = Some(node.brett.get_statistik().to_number(Spieler::Computer));
+            let halbzuege = match damen_endspiel_regel {
+                true => node.damen_endspiel,
+                false => 0,
+            };
+            let datenbank_wert = datenbank
+                .and_then(|datenbank| datenbank.get_wert_nach(&node.brett, node.am_zug, halbzuege));
+            node.bewertung = Some(match datenbank_wert {
+                Some(wert) if node.am_zug == am_zug => wert.to_bewertung(),
+                Some(wert) => -wert.to_bewertung(),
+                None => gewichte.bewerte(&node.brett, am_zug),
+            });
         }
 
         // Bewertungen der Zeilen darüber berechnen
-        for depth in (0..=max_depth-1).rev() {
+        for depth in (0..=max_depth - 1).rev() {
             for node_index in 0..nodes.len() {
                 let node = &nodes[node_index];
-                if node.depth != depth {
+                if node.depth != depth || node.children.is_empty() {
                     continue;
                 }
 
@@ -91,18 +443,29 @@ impl SpielBrett {
                     let child = &nodes[child_index];
                     let child_bewertung = child.bewertung.unwrap();
                     best_child = Some(match best_child {
-                        None => BestChild { index: child_index, bewertung: child_bewertung },
-                        Some(best_child) => match node.am_zug {
-                            Spieler::Computer => if best_child.bewertung > child_bewertung {
-                                best_child
-                            } else {
-                                BestChild { index: child_index, bewertung: child_bewertung }
-                            },
-                            Spieler::Mensch => if best_child.bewertung < child_bewertung {
+                        None => BestChild {
+                            index: child_index,
+                            bewertung: child_bewertung,
+                        },
+                        // Der Spieler an der Wurzel maximiert, sein Gegner minimiert
+                        Some(best_child) => {
+                            if node.am_zug == am_zug {
+                                if best_child.bewertung > child_bewertung {
+                                    best_child
+                                } else {
+                                    BestChild {
+                                        index: child_index,
+                                        bewertung: child_bewertung,
+                                    }
+                                }
+                            } else if best_child.bewertung < child_bewertung {
                                 best_child
                             } else {
-                                BestChild { index: child_index, bewertung: child_bewertung }
-                            },
+                                BestChild {
+                                    index: child_index,
+                                    bewertung: child_bewertung,
+                                }
+                            }
                         }
                     });
                 }
@@ -113,6 +476,222 @@ impl SpielBrett {
             }
         }
 
-        nodes[nodes[0].best_child.unwrap()].brett.clone()
+        let mut hauptvariante = Vec::new();
+        let mut node = &nodes[0];
+        while let Some(best_child) = node.best_child {
+            node = &nodes[best_child];
+            hauptvariante.push(node.brett.clone());
+        }
+
+        Some(SuchErgebnis {
+            // Ohne möglichen Zug bleibt das Brett, wie es ist
+            best_move: hauptvariante.first().unwrap_or(self).clone(),
+            bewertung: nodes[0].bewertung.unwrap(),
+            hauptvariante,
+            depth: max_depth,
+            nodes: nodes.len(),
+            zeit: start.get_vergangen(),
+        })
+    }
+}
+
+impl Partie {
+    /// Wie `SpielBrett::get_best_move_with` für den Spieler am Zug, wobei die Suche
+    /// Stellungswiederholungen und die Regel für Züge ohne Fortschritt als Remis bewertet.
+    pub fn get_best_move_with(
+        &self,
+        max_depth: u32,
+        buch: Option<&Eroeffnungsbuch>,
+        datenbank: Option<&Endspieldatenbank>,
+        zufall: Option<&mut Zufall>,
+    ) -> SuchErgebnis {
+        let einstellungen = SuchEinstellungen::new(max_depth);
+        self.get_best_move_mit_einstellungen(&einstellungen, buch, datenbank, zufall)
+    }
+
+    pub fn get_best_move_mit_einstellungen(
+        &self,
+        einstellungen: &SuchEinstellungen,
+        buch: Option<&Eroeffnungsbuch>,
+        datenbank: Option<&Endspieldatenbank>,
+        zufall: Option<&mut Zufall>,
+    ) -> SuchErgebnis {
+        self.get_brett().get_best_move_in_partie(
+            self.get_am_zug(),
+            einstellungen,
+            buch,
+            datenbank,
+            zufall,
+            Some(self),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::brett::Remisregeln;
+
+    #[test]
+    fn test_suche() {
+        let brett = SpielBrett::parse(concat!(
+            "_ _ _ _ \n",
+            " _ _ _ _\n",
+            "_ _ m _ \n",
+            " _ _ _ _\n",
+            "_ _ _ _ \n",
+            " _ _ _ _\n",
+            "_ _ _ m \n",
+            " _ _ _ c",
+        ))
+        .unwrap();
+
+        let ergebnis = brett.suche(Spieler::Weiss, 3);
+        assert_eq!(
+            ergebnis.best_move,
+            brett.find_zug(Spieler::Weiss, "32x23").unwrap()
+        );
+        assert_eq!(ergebnis.hauptvariante.len(), 3);
+        assert_eq!(ergebnis.hauptvariante[0], ergebnis.best_move);
+        assert_eq!(ergebnis.depth, 3);
+        assert!(ergebnis.nodes > 3);
+        assert!(ergebnis.bewertung >= 0);
+
+        let ergebnis = brett.suche(Spieler::Schwarz, 2);
+        assert!(brett
+            .get_possible_moves(Spieler::Schwarz)
+            .contains(&ergebnis.best_move));
+        assert!(ergebnis.bewertung > 0);
+    }
+
+    #[test]
+    fn test_suche_blockiert() {
+        let brett = SpielBrett::parse(concat!(
+            "_ _ _ m \n",
+            " _ _ _ c\n",
+            "_ _ _ _ \n",
+            " _ m _ _\n",
+            "_ _ _ _ \n",
+            " _ _ _ _\n",
+            "_ _ _ _ \n",
+            " _ _ _ _",
+        ))
+        .unwrap();
+
+        // Wer nicht ziehen kann, hat verloren und zieht nicht
+        let ergebnis = brett.suche(Spieler::Weiss, 3);
+        assert_eq!(ergebnis.bewertung, i32::MIN);
+        assert_eq!(ergebnis.best_move, brett);
+        assert!(ergebnis.hauptvariante.is_empty());
+
+        // Schwarz hält den Stein blockiert
+        let ergebnis = brett.suche(Spieler::Schwarz, 2);
+        assert_eq!(ergebnis.bewertung, i32::MAX);
+        assert!(ergebnis
+            .best_move
+            .get_possible_moves(Spieler::Weiss)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_suche_remis() {
+        let brett = SpielBrett::parse(concat!(
+            "_ _ _ M \n",
+            " _ _ _ _\n",
+            "_ _ _ _ \n",
+            " _ _ _ _\n",
+            "_ _ _ _ \n",
+            " _ _ _ _\n",
+            "_ _ _ _ \n",
+            " C _ _ C",
+        ))
+        .unwrap();
+        assert_eq!(brett.suche(Spieler::Weiss, 3).bewertung, 300);
+
+        let regeln = Remisregeln {
+            max_zuege_ohne_fortschritt: Some(1),
+            ..Remisregeln::default()
+        };
+        let partie = Partie::new(brett.clone(), Spieler::Weiss, regeln);
+        assert_eq!(partie.get_best_move_with(3, None, None, None).bewertung, 0);
+
+        // Nach 8 Halbzügen ohne Schlagen endet das Endspiel innerhalb der Suche unentschieden
+        let mut partie = Partie::new(brett, Spieler::Weiss, Remisregeln::default());
+        for _ in 0..8 {
+            let am_zug = partie.get_am_zug();
+            let (_, nachher) = partie
+                .get_brett()
+                .get_possible_zuege(am_zug)
+                .into_iter()
+                .find(|(zug, brett)| {
+                    !zug.is_schlagen()
+                        && !partie.get_verlauf().contains(&brett.get_hash(!am_zug))
+                        && brett
+                            .get_possible_zuege(!am_zug)
+                            .iter()
+                            .all(|(zug, _)| !zug.is_schlagen())
+                })
+                .unwrap();
+            partie.ziehe(nachher).unwrap();
+        }
+        assert_eq!(partie.get_best_move_with(3, None, None, None).bewertung, 0);
+        let partie = Partie::new(
+            partie.get_brett().clone(),
+            Spieler::Weiss,
+            Remisregeln {
+                damen_endspiel: false,
+                ..Remisregeln::default()
+            },
+        );
+        assert_eq!(
+            partie.get_best_move_with(3, None, None, None).bewertung,
+            300
+        );
+    }
+
+    #[test]
+    fn test_suche_einstellungen() {
+        let partie = Partie::new(
+            SpielBrett::startaufstellung(),
+            Spieler::Schwarz,
+            Remisregeln::default(),
+        );
+        let einstellungen = SuchEinstellungen {
+            max_depth: 20,
+            max_zeit: Some(Duration::from_millis(50)),
+            gewichte: Gewichte {
+                vorgerueckt: 5,
+                ..Gewichte::default()
+            },
+        };
+        let ergebnis = partie.get_best_move_mit_einstellungen(&einstellungen, None, None, None);
+        assert!(ergebnis.depth >= 1 && ergebnis.depth < 20);
+        assert!(SpielBrett::startaufstellung()
+            .get_possible_moves(Spieler::Schwarz)
+            .contains(&ergebnis.best_move));
+
+        // Ohne Tiefe begrenzt nur die Zeit die Suche
+        let einstellungen = SuchEinstellungen::parse("zeit=50").unwrap();
+        assert_eq!(einstellungen.max_depth, u32::MAX);
+        let ergebnis = partie.get_best_move_mit_einstellungen(&einstellungen, None, None, None);
+        assert!(ergebnis.depth >= 1);
+        assert!(ergebnis.zeit < Duration::from_millis(500));
+        assert_eq!(ergebnis.hauptvariante.len() as u32, ergebnis.depth);
+
+        // Ein Endspiel, in dem jede Variante nach wenigen Zügen endet
+        let brett = SpielBrett::parse(concat!(
+            "_ _ _ m \n",
+            " _ _ _ c\n",
+            "_ _ _ _ \n",
+            " _ _ _ _\n",
+            "_ _ _ _ \n",
+            " _ _ _ _\n",
+            "_ _ _ _ \n",
+            " _ _ _ _",
+        ))
+        .unwrap();
+        let partie = Partie::new(brett, Spieler::Schwarz, Remisregeln::default());
+        let ergebnis = partie.get_best_move_mit_einstellungen(&einstellungen, None, None, None);
+        assert!(ergebnis.depth < 100);
     }
 }
@@ -1,7 +1,7 @@
-use std::cmp::Ordering;
-
 use crate::brett::{Feld, Position, SpielBrett, Spieler};
 
+use super::Gewichte;
+
 #[derive(Clone, Copy, Debug)]
 pub struct SpielerStatistik {
     pub steine: u32,
@@ -9,31 +9,32 @@ pub struct SpielerStatistik {
 }
 
 impl SpielerStatistik {
-    fn to_number(self) -> i32 {
-        match self.steine as i32 + self.damen as i32 * 3 {
-            0 => i32::MIN,
-            number => number,
+    fn to_number(self, gewichte: &Gewichte) -> i32 {
+        // Wer keine Figuren mehr hat, hat verloren
+        if self.steine + self.damen == 0 {
+            return i32::MIN;
         }
+        self.steine as i32 * gewichte.stein + self.damen as i32 * gewichte.dame
     }
 }
 
 #[derive(Clone, Copy, Debug)]
 pub struct Statistik {
-    pub computer: SpielerStatistik,
-    pub mensch: SpielerStatistik,
+    pub weiss: SpielerStatistik,
+    pub schwarz: SpielerStatistik,
 }
 
 impl Statistik {
-    pub fn to_number(self, perspektive: Spieler) -> i32 {
+    pub fn to_number_mit(self, perspektive: Spieler, gewichte: &Gewichte) -> i32 {
         match perspektive {
-            Spieler::Mensch => self
-                .mensch
-                .to_number()
-                .saturating_sub(self.computer.to_number()),
-            Spieler::Computer => self
-                .computer
-                .to_number()
-                .saturating_sub(self.mensch.to_number()),
+            Spieler::Schwarz => self
+                .schwarz
+                .to_number(gewichte)
+                .saturating_sub(self.weiss.to_number(gewichte)),
+            Spieler::Weiss => self
+                .weiss
+                .to_number(gewichte)
+                .saturating_sub(self.schwarz.to_number(gewichte)),
         }
     }
 }
@@ -57,13 +58,13 @@ impl SpielBrett {
 
     pub fn get_statistik(&self) -> Statistik {
         Statistik {
-            computer: SpielerStatistik {
-                steine: self.count_felder(Feld::Stein(Spieler::Computer)),
-                damen: self.count_felder(Feld::Dame(Spieler::Computer)),
+            weiss: SpielerStatistik {
+                steine: self.count_felder(Feld::Stein(Spieler::Weiss)),
+                damen: self.count_felder(Feld::Dame(Spieler::Weiss)),
             },
-            mensch: SpielerStatistik {
-                steine: self.count_felder(Feld::Stein(Spieler::Mensch)),
-                damen: self.count_felder(Feld::Dame(Spieler::Mensch)),
+            schwarz: SpielerStatistik {
+                steine: self.count_felder(Feld::Stein(Spieler::Schwarz)),
+                damen: self.count_felder(Feld::Dame(Spieler::Schwarz)),
             },
         }
     }
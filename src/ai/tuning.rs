@@ -0,0 +1,177 @@
+use crate::{
+    brett::Spieler,
+    pdn::{Ergebnis, PdnSpiel},
+};
+
+use super::Gewichte;
+
+/// Eine Stellung aus einer Partie mit bekanntem Ausgang. Merkmale und Ergebnis sind aus Sicht
+/// von Weiß, das Ergebnis ist 1 für einen Sieg, 0.5 für Remis und 0 für eine Niederlage.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct TuningStellung {
+    pub merkmale: [i32; 5],
+    pub ergebnis: f64,
+}
+
+/// Sammelt alle ruhigen Stellungen aus Spielen mit Ergebnis. Stellungen, in denen geschlagen
+/// werden muss, werden übersprungen, weil die Bewertung dort wenig über den Ausgang sagt.
+pub fn get_tuning_stellungen(spiele: &[PdnSpiel]) -> Vec<TuningStellung> {
+    let mut result = Vec::new();
+    for spiel in spiele {
+        let ergebnis = match spiel.ergebnis {
+            Ergebnis::Sieg(Spieler::Weiss) => 1.0,
+            Ergebnis::Sieg(Spieler::Schwarz) => 0.0,
+            Ergebnis::Unentschieden => 0.5,
+            Ergebnis::Offen => continue,
+        };
+        for (brett, am_zug, _) in spiel.get_zuege() {
+            if brett
+                .get_possible_zuege(am_zug)
+                .iter()
+                .any(|(zug, _)| zug.is_schlagen())
+            {
+                continue;
+            }
+            result.push(TuningStellung {
+                merkmale: Gewichte::get_merkmale(brett, Spieler::Weiss),
+                ergebnis,
+            });
+        }
+    }
+    result
+}
+
+fn get_bewertung(gewichte: &[f64; 5], merkmale: &[i32; 5]) -> f64 {
+    gewichte
+        .iter()
+        .zip(merkmale)
+        .map(|(gewicht, &merkmal)| gewicht * merkmal as f64)
+        .sum()
+}
+
+/// Die vorhergesagte Gewinnwahrscheinlichkeit für eine Bewertung in Hundertstel Steinen
+fn get_vorhersage(k: f64, bewertung: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-k * bewertung / 400.0))
+}
+
+fn get_fehler_f64(stellungen: &[TuningStellung], k: f64, gewichte: &[f64; 5]) -> f64 {
+    let summe: f64 = stellungen
+        .iter()
+        .map(|stellung| {
+            let vorhersage = get_vorhersage(k, get_bewertung(gewichte, &stellung.merkmale));
+            (stellung.ergebnis - vorhersage).powi(2)
+        })
+        .sum();
+    summe / stellungen.len().max(1) as f64
+}
+
+fn to_f64(gewichte: &Gewichte) -> [f64; 5] {
+    gewichte.to_array().map(|gewicht| gewicht as f64)
+}
+
+/// Der mittlere quadratische Fehler zwischen vorhergesagtem und tatsächlichem Ergebnis
+pub fn get_fehler(stellungen: &[TuningStellung], k: f64, gewichte: &Gewichte) -> f64 {
+    get_fehler_f64(stellungen, k, &to_f64(gewichte))
+}
+
+/// Sucht den Skalierungsfaktor `k`, mit dem die Bewertungen von `gewichte` die Ergebnisse am
+/// besten vorhersagen.
+pub fn find_k(stellungen: &[TuningStellung], gewichte: &Gewichte) -> f64 {
+    let gewichte = to_f64(gewichte);
+    let (mut min, mut max) = (0.0, 10.0);
+    // Ternäre Suche, der Fehler hat in `k` nur ein Minimum
+    for _ in 0..100 {
+        let links = min + (max - min) / 3.0;
+        let rechts = max - (max - min) / 3.0;
+        if get_fehler_f64(stellungen, links, &gewichte)
+            < get_fehler_f64(stellungen, rechts, &gewichte)
+        {
+            max = rechts;
+        } else {
+            min = links;
+        }
+    }
+    (min + max) / 2.0
+}
+
+/// Passt die Gewichte mit dem Gradientenverfahren (Adam) so an, dass der Fehler für den festen
+/// Faktor `k` möglichst klein wird. Das Gewicht eines Steins bleibt als Maßstab unverändert.
+pub fn tune(
+    stellungen: &[TuningStellung],
+    k: f64,
+    start: &Gewichte,
+    iterationen: u32,
+    mut fortschritt: impl FnMut(u32, f64),
+) -> Gewichte {
+    const LERNRATE: f64 = 1.0;
+    const BETA1: f64 = 0.9;
+    const BETA2: f64 = 0.999;
+
+    let mut gewichte = to_f64(start);
+    let mut moment = [0.0; 5];
+    let mut varianz = [0.0; 5];
+    let faktor = k * 10f64.ln() / 400.0;
+
+    for iteration in 1..=iterationen {
+        let mut gradient = [0.0; 5];
+        for stellung in stellungen {
+            let vorhersage = get_vorhersage(k, get_bewertung(&gewichte, &stellung.merkmale));
+            let ableitung =
+                -2.0 * (stellung.ergebnis - vorhersage) * vorhersage * (1.0 - vorhersage) * faktor;
+            for (gradient, &merkmal) in gradient.iter_mut().zip(&stellung.merkmale) {
+                *gradient += ableitung * merkmal as f64;
+            }
+        }
+
+        for index in 1..gewichte.len() {
+            let gradient = gradient[index] / stellungen.len().max(1) as f64;
+            moment[index] = BETA1 * moment[index] + (1.0 - BETA1) * gradient;
+            varianz[index] = BETA2 * varianz[index] + (1.0 - BETA2) * gradient * gradient;
+            let moment = moment[index] / (1.0 - BETA1.powi(iteration as i32));
+            let varianz = varianz[index] / (1.0 - BETA2.powi(iteration as i32));
+            gewichte[index] -= LERNRATE * moment / (varianz.sqrt() + 1e-12);
+        }
+
+        if iteration % 100 == 0 || iteration == iterationen {
+            fortschritt(iteration, get_fehler_f64(stellungen, k, &gewichte));
+        }
+    }
+
+    Gewichte::from_array(gewichte.map(|gewicht| gewicht.round() as i32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tuning() {
+        let spiele = PdnSpiel::parse_alle(concat!(
+            "1. 10-14 23-19 2. 14x23 27x20 2-0\n",
+            "[Event \"2\"]\n",
+            "1. 10-14 23-19 *\n",
+        ))
+        .unwrap();
+        // Vor 14x23 und 27x20 muss geschlagen werden, das Spiel ohne Ergebnis zählt nicht
+        let stellungen = get_tuning_stellungen(&spiele);
+        assert_eq!(stellungen.len(), 2);
+        assert!(stellungen.iter().all(|stellung| stellung.ergebnis == 1.0));
+
+        // Wer weiter vorgerückt ist, gewinnt in diesen Stellungen
+        let mut stellungen = Vec::new();
+        for vorgerueckt in -3..=3 {
+            for _ in 0..10 {
+                stellungen.push(TuningStellung {
+                    merkmale: [0, 0, vorgerueckt, 0, 0],
+                    ergebnis: 0.5 + vorgerueckt as f64 / 8.0,
+                });
+            }
+        }
+        let start = Gewichte::default();
+        let k = find_k(&stellungen, &start);
+        let gewichte = tune(&stellungen, k, &start, 300, |_, _| ());
+        assert_eq!(gewichte.stein, start.stein);
+        assert!(gewichte.vorgerueckt > 0);
+        assert!(get_fehler(&stellungen, k, &gewichte) < get_fehler(&stellungen, k, &start));
+    }
+}
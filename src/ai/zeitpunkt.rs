@@ -0,0 +1,91 @@
+use std::{ops::Add, time::Duration};
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+// Unter WebAssembly im Browser gibt es weder `Instant` noch `SystemTime`, beide brechen dort
+// mit einem Panic ab. Die Zeit kommt dann aus `Date.now()` von JavaScript.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen::prelude::wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = Date)]
+    fn now() -> f64;
+}
+
+/// Ein Zeitpunkt für die Zeitmessung der Suche, der auch unter WebAssembly funktioniert
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug)]
+pub struct Zeitpunkt {
+    #[cfg(not(target_arch = "wasm32"))]
+    instant: Instant,
+    /// Millisekunden seit 1970
+    #[cfg(target_arch = "wasm32")]
+    millis: f64,
+}
+
+impl Zeitpunkt {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn jetzt() -> Self {
+        Self {
+            instant: Instant::now(),
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn jetzt() -> Self {
+        Self { millis: now() }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn get_vergangen(self) -> Duration {
+        self.instant.elapsed()
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn get_vergangen(self) -> Duration {
+        Duration::from_secs_f64((now() - self.millis).max(0.0) / 1000.0)
+    }
+}
+
+impl Add<Duration> for Zeitpunkt {
+    type Output = Self;
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn add(self, dauer: Duration) -> Self {
+        Self {
+            instant: self.instant + dauer,
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn add(self, dauer: Duration) -> Self {
+        Self {
+            millis: self.millis + dauer.as_secs_f64() * 1000.0,
+        }
+    }
+}
+
+/// Die Zeit seit 1970, zum Beispiel als Startwert für Zufallszahlen
+#[cfg(not(target_arch = "wasm32"))]
+pub fn get_systemzeit() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn get_systemzeit() -> Duration {
+    Duration::from_secs_f64(now() / 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zeitpunkt() {
+        let start = Zeitpunkt::jetzt();
+        assert!(start + Duration::from_secs(1) > Zeitpunkt::jetzt());
+        assert!(start.get_vergangen() < Duration::from_secs(1));
+        assert!(get_systemzeit() > Duration::from_secs(1_000_000_000));
+    }
+}
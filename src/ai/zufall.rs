@@ -0,0 +1,34 @@
+use super::get_systemzeit;
+
+/// Einfacher xorshift-Zufallsgenerator, damit keine zusätzliche Abhängigkeit nötig ist.
+#[derive(Clone, Debug)]
+pub struct Zufall {
+    zustand: u64,
+}
+
+impl Zufall {
+    pub fn new(seed: u64) -> Self {
+        Self { zustand: seed | 1 }
+    }
+
+    pub fn from_zeit() -> Self {
+        Self::new(get_systemzeit().as_nanos() as u64)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let mut x = self.zustand;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.zustand = x;
+        x
+    }
+
+    /// Eine Zahl aus `0..max`
+    pub fn next_bis(&mut self, max: u64) -> u64 {
+        if max == 0 {
+            return 0;
+        }
+        self.next_u64() % max
+    }
+}
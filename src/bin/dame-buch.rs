@@ -0,0 +1,47 @@
+use std::{env, fs, process::ExitCode};
+
+use dame::{ai::Eroeffnungsbuch, pdn::PdnSpiel};
+
+const MAX_HALBZUEGE: usize = 16;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (ausgabe, pdn_dateien) = match args.split_first() {
+        Some((ausgabe, pdn_dateien)) if !pdn_dateien.is_empty() => (ausgabe, pdn_dateien),
+        _ => {
+            eprintln!("Verwendung: dame-buch <ausgabe> <spiele.pdn>...");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut spiele = Vec::new();
+    for datei in pdn_dateien {
+        let text = match fs::read_to_string(datei) {
+            Ok(text) => text,
+            Err(err) => {
+                eprintln!("{datei} konnte nicht gelesen werden: {err}");
+                return ExitCode::FAILURE;
+            }
+        };
+        match PdnSpiel::parse_alle(&text) {
+            Some(neue_spiele) => spiele.extend(neue_spiele),
+            None => {
+                eprintln!("{datei} enthält ungültiges PDN");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let buch = Eroeffnungsbuch::from_spiele(&spiele, MAX_HALBZUEGE);
+    if let Err(err) = fs::write(ausgabe, buch.to_text()) {
+        eprintln!("{ausgabe} konnte nicht geschrieben werden: {err}");
+        return ExitCode::FAILURE;
+    }
+
+    println!(
+        "{} Spiele gelesen, {} Stellungen im Buch",
+        spiele.len(),
+        buch.len()
+    );
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,148 @@
+use std::{
+    env,
+    net::{TcpListener, TcpStream},
+    process::ExitCode,
+    time::Duration,
+};
+
+use dame::{
+    ai::{Endspieldatenbank, Eroeffnungsbuch, SuchEinstellungen},
+    brett::Spieler,
+    dxp::{DxpEngine, DxpMeldung, DxpVerbindung, Spielanfrage},
+    pdn::Ergebnis,
+};
+
+const VERWENDUNG: &str = "Verwendung: dame-dxp server <port> <engine>
+       dame-dxp client <adresse:port> <engine> <spiele> <minuten> [zuege]
+Als Server wartet die Engine auf Einladungen, als Client lädt sie zu Partien ein und wechselt
+dabei die Farben. Eine Engine wird wie bei dame-match als \"tiefe=20,zeit=2000\" angegeben.";
+const EROEFFNUNGSBUCH_DATEI: &str = "eroeffnungsbuch.txt";
+const ENDSPIELDATENBANK_DATEI: &str = "endspiel.edb";
+/// Für so viele Züge gilt die Bedenkzeit, wenn nichts angegeben ist
+const STANDARD_ZUEGE: u32 = 75;
+
+fn zeige(meldung: DxpMeldung) {
+    match meldung {
+        DxpMeldung::Chat(text) => println!("Chat: {text}"),
+        DxpMeldung::Abgelehnt { name, annahme } => println!("{name} lehnt ab: {annahme:?}"),
+        DxpMeldung::Partie(name) => println!("Partie gegen {name}"),
+        DxpMeldung::Unbekannt(text) => eprintln!("DXP: unbekannte Nachricht {text:?}"),
+    }
+}
+
+fn server(port: u16, engine: &DxpEngine) -> ExitCode {
+    let listener = match TcpListener::bind(("0.0.0.0", port)) {
+        Ok(listener) => listener,
+        Err(err) => {
+            eprintln!("Port {port} kann nicht geöffnet werden: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    println!("Warte auf Port {port}");
+    for stream in listener.incoming() {
+        let ergebnisse = stream
+            .and_then(DxpVerbindung::new)
+            .and_then(|mut verbindung| engine.spiele_als_follower(&mut verbindung, zeige));
+        match ergebnisse {
+            Ok(ergebnisse) => println!("{} Partien gespielt", ergebnisse.len()),
+            Err(err) => eprintln!("Verbindung abgebrochen: {err}"),
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn client(adresse: &str, engine: &DxpEngine, spiele: u32, anfrage: Spielanfrage) -> ExitCode {
+    let verbindung = TcpStream::connect(adresse).and_then(DxpVerbindung::new);
+    let mut verbindung = match verbindung {
+        Ok(verbindung) => verbindung,
+        Err(err) => {
+            eprintln!("{adresse} ist nicht erreichbar: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let (mut siege, mut remis, mut niederlagen) = (0, 0, 0);
+    for runde in 0..spiele {
+        let eigene_farbe = if runde % 2 == 0 {
+            Spieler::Schwarz
+        } else {
+            Spieler::Weiss
+        };
+        let anfrage = Spielanfrage {
+            follower_farbe: !eigene_farbe,
+            ..anfrage.clone()
+        };
+        let weiter = runde + 1 < spiele;
+        let ergebnis = match engine.spiele_als_initiator(&mut verbindung, &anfrage, weiter, zeige) {
+            Ok(Some(ergebnis)) => ergebnis,
+            Ok(None) => return ExitCode::FAILURE,
+            Err(err) => {
+                eprintln!("Verbindung abgebrochen: {err}");
+                return ExitCode::FAILURE;
+            }
+        };
+        match ergebnis {
+            Ergebnis::Sieg(spieler) if spieler == eigene_farbe => siege += 1,
+            Ergebnis::Sieg(_) => niederlagen += 1,
+            _ => remis += 1,
+        }
+        println!(
+            "Spiel {}/{spiele}: {}, +{siege} ={remis} -{niederlagen}",
+            runde + 1,
+            ergebnis.to_pdn()
+        );
+    }
+    ExitCode::SUCCESS
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (modus, adresse, engine, rest) = match args.as_slice() {
+        [modus, adresse, engine, rest @ ..] => (modus.as_str(), adresse, engine, rest),
+        _ => {
+            eprintln!("{VERWENDUNG}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let Some(einstellungen) = SuchEinstellungen::parse(engine) else {
+        eprintln!("Ungültige Engine\n{VERWENDUNG}");
+        return ExitCode::FAILURE;
+    };
+    let mut engine = DxpEngine::new("Dame", einstellungen);
+    engine.buch = Eroeffnungsbuch::load(EROEFFNUNGSBUCH_DATEI);
+    engine.datenbank = Endspieldatenbank::load(ENDSPIELDATENBANK_DATEI);
+
+    match (modus, rest) {
+        ("server", []) => match adresse.parse() {
+            Ok(port) => server(port, &engine),
+            Err(_) => {
+                eprintln!("Der Port muss eine Zahl sein");
+                ExitCode::FAILURE
+            }
+        },
+        ("client", [spiele, minuten, zuege @ ..]) if zuege.len() <= 1 => {
+            let zahlen = (
+                spiele.parse(),
+                minuten.parse::<u64>(),
+                zuege
+                    .first()
+                    .map_or(Ok(STANDARD_ZUEGE), |zuege| zuege.parse()),
+            );
+            let (Ok(spiele), Ok(minuten), Ok(zuege)) = zahlen else {
+                eprintln!("Spiele, Minuten und Züge müssen Zahlen sein");
+                return ExitCode::FAILURE;
+            };
+            let anfrage = Spielanfrage {
+                name: engine.name.clone(),
+                follower_farbe: Spieler::Weiss,
+                bedenkzeit: Duration::from_secs(60 * minuten),
+                zuege,
+                start: None,
+            };
+            client(adresse, &engine, spiele, anfrage)
+        }
+        _ => {
+            eprintln!("{VERWENDUNG}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use std::{env, fs, process::ExitCode, time::Instant};
+
+use dame::ai::Endspieldatenbank;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (ausgabe, max_figuren) = match args.as_slice() {
+        [ausgabe, max_figuren] => match max_figuren.parse::<usize>() {
+            Ok(max_figuren) if max_figuren >= 2 => (ausgabe, max_figuren),
+            _ => {
+                eprintln!("Die Anzahl der Figuren muss mindestens 2 sein");
+                return ExitCode::FAILURE;
+            }
+        },
+        _ => {
+            eprintln!("Verwendung: dame-endspiel <ausgabe> <max_figuren>");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let start = Instant::now();
+    let datenbank = Endspieldatenbank::generate(max_figuren, |material| {
+        println!("[{:>6.1?}] Berechne {:?}", start.elapsed(), material.0);
+    });
+
+    if let Err(err) = fs::write(ausgabe, datenbank.to_bytes()) {
+        eprintln!("{ausgabe} konnte nicht geschrieben werden: {err}");
+        return ExitCode::FAILURE;
+    }
+    println!("Fertig nach {:.1?}", start.elapsed());
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,182 @@
+use std::{env, fs, process::ExitCode};
+
+use dame::{
+    ai::SuchEinstellungen,
+    brett::{Partie, Remisregeln, SpielBrett, Spieler, Spielstand},
+    pdn::{Ergebnis, PdnSpiel},
+};
+
+const VERWENDUNG: &str = "Verwendung: dame-match <ausgabe.pdn> <spiele> <engine_a> <engine_b> \
+[startstellungen.pdn]
+Eine Engine wird als Liste wie \"tiefe=5,zeit=500,gewichte=datei.txt,dame=350\" angegeben.
+Die Zeit ist in Millisekunden pro Zug, einzelne Gewichte überschreiben die aus der Datei.";
+
+/// Nach so vielen Halbzügen wird ein Spiel als Remis gewertet
+const MAX_HALBZUEGE: usize = 300;
+
+/// Alle Stellungen nach dem ersten Zug beider Seiten
+fn get_standard_startstellungen() -> Vec<(SpielBrett, Spieler)> {
+    let start = SpielBrett::startaufstellung();
+    let mut result = Vec::new();
+    for (_, nach_schwarz) in start.get_possible_zuege(Spieler::Schwarz) {
+        for (_, nach_weiss) in nach_schwarz.get_possible_zuege(Spieler::Weiss) {
+            result.push((nach_weiss, Spieler::Schwarz));
+        }
+    }
+    result
+}
+
+/// Spielt ein Spiel, in dem `engine_schwarz` für Schwarz und `engine_weiss` für Weiß
+/// zieht.
+fn spiele(
+    start: &SpielBrett,
+    start_am_zug: Spieler,
+    engine_schwarz: &SuchEinstellungen,
+    engine_weiss: &SuchEinstellungen,
+) -> PdnSpiel {
+    let mut spiel = PdnSpiel::new(start.clone(), start_am_zug);
+    let mut partie = Partie::new(start.clone(), start_am_zug, Remisregeln::default());
+    loop {
+        match partie.get_spielstand() {
+            Spielstand::Laeuft => (),
+            Spielstand::Gewonnen(spieler) => {
+                spiel.ergebnis = Ergebnis::Sieg(spieler);
+                break;
+            }
+            Spielstand::Remis(_) => {
+                spiel.ergebnis = Ergebnis::Unentschieden;
+                break;
+            }
+        }
+        if spiel.stellungen.len() >= MAX_HALBZUEGE {
+            spiel.ergebnis = Ergebnis::Unentschieden;
+            break;
+        }
+
+        let engine = match partie.get_am_zug() {
+            Spieler::Schwarz => engine_schwarz,
+            Spieler::Weiss => engine_weiss,
+        };
+        let ergebnis = partie.get_best_move_mit_einstellungen(engine, None, None, None);
+        partie.ziehe(ergebnis.best_move.clone()).unwrap();
+        spiel.stellungen.push(ergebnis.best_move);
+    }
+    spiel
+}
+
+/// Die Elo-Differenz, die zu einem erwarteten Punkteanteil gehört
+fn get_elo_differenz(punkte_anteil: f64) -> f64 {
+    let punkte_anteil = punkte_anteil.clamp(1e-6, 1.0 - 1e-6);
+    -400.0 * (1.0 / punkte_anteil - 1.0).log10()
+}
+
+/// Elo-Differenz von A gegenüber B und der Fehler für ein 95%-Konfidenzintervall
+fn get_elo(siege: u32, remis: u32, niederlagen: u32) -> (f64, f64) {
+    let spiele = (siege + remis + niederlagen) as f64;
+    let punkte_anteil = (siege as f64 + remis as f64 / 2.0) / spiele;
+    let varianz = (siege as f64 * (1.0 - punkte_anteil).powi(2)
+        + remis as f64 * (0.5 - punkte_anteil).powi(2)
+        + niederlagen as f64 * punkte_anteil.powi(2))
+        / spiele;
+    let fehler = 1.96 * (varianz / spiele).sqrt();
+    let elo = get_elo_differenz(punkte_anteil);
+    let elo_fehler = (get_elo_differenz(punkte_anteil + fehler)
+        - get_elo_differenz(punkte_anteil - fehler))
+        / 2.0;
+    (elo, elo_fehler)
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (ausgabe, anzahl, engine_a, engine_b, startstellungen) = match args.as_slice() {
+        [ausgabe, anzahl, engine_a, engine_b, rest @ ..] if rest.len() <= 1 => {
+            (ausgabe, anzahl, engine_a, engine_b, rest.first())
+        }
+        _ => {
+            eprintln!("{VERWENDUNG}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let Ok(anzahl) = anzahl.parse::<usize>() else {
+        eprintln!("Die Anzahl der Spiele muss eine Zahl sein");
+        return ExitCode::FAILURE;
+    };
+    let (Some(einstellungen_a), Some(einstellungen_b)) = (
+        SuchEinstellungen::parse(engine_a),
+        SuchEinstellungen::parse(engine_b),
+    ) else {
+        eprintln!("Ungültige Engine\n{VERWENDUNG}");
+        return ExitCode::FAILURE;
+    };
+
+    let startstellungen = match startstellungen {
+        None => get_standard_startstellungen(),
+        Some(datei) => {
+            let spiele = match fs::read_to_string(datei)
+                .ok()
+                .and_then(|text| PdnSpiel::parse_alle(&text))
+            {
+                Some(spiele) if !spiele.is_empty() => spiele,
+                _ => {
+                    eprintln!("{datei} konnte nicht gelesen werden oder enthält ungültiges PDN");
+                    return ExitCode::FAILURE;
+                }
+            };
+            spiele
+                .iter()
+                .map(|spiel| (spiel.get_brett().clone(), spiel.get_am_zug()))
+                .collect()
+        }
+    };
+
+    let (mut siege, mut remis, mut niederlagen) = (0, 0, 0);
+    let mut pdn = String::new();
+    for runde in 0..anzahl {
+        // Jede Startstellung wird zweimal mit vertauschten Farben gespielt
+        let (start, start_am_zug) = &startstellungen[runde / 2 % startstellungen.len()];
+        let a_spielt = if runde % 2 == 0 {
+            Spieler::Schwarz
+        } else {
+            Spieler::Weiss
+        };
+        let mut spiel = match a_spielt {
+            Spieler::Schwarz => spiele(start, *start_am_zug, &einstellungen_a, &einstellungen_b),
+            Spieler::Weiss => spiele(start, *start_am_zug, &einstellungen_b, &einstellungen_a),
+        };
+
+        let (schwarz, weiss) = match a_spielt {
+            Spieler::Schwarz => (engine_a, engine_b),
+            Spieler::Weiss => (engine_b, engine_a),
+        };
+        spiel.set_tag("Event", "dame-match");
+        spiel.set_tag("Round", &(runde + 1).to_string());
+        spiel.set_tag("Black", schwarz);
+        spiel.set_tag("White", weiss);
+        spiel.set_tag("Result", spiel.ergebnis.to_pdn());
+        pdn.push_str(&spiel.to_pdn());
+        pdn.push('\n');
+
+        match spiel.ergebnis {
+            Ergebnis::Sieg(spieler) if spieler == a_spielt => siege += 1,
+            Ergebnis::Sieg(_) => niederlagen += 1,
+            _ => remis += 1,
+        }
+        println!(
+            "Spiel {}/{anzahl}: {} nach {} Halbzügen, A +{siege} ={remis} -{niederlagen}",
+            runde + 1,
+            spiel.ergebnis.to_pdn(),
+            spiel.stellungen.len(),
+        );
+    }
+
+    if let Err(err) = fs::write(ausgabe, pdn) {
+        eprintln!("{ausgabe} konnte nicht geschrieben werden: {err}");
+        return ExitCode::FAILURE;
+    }
+
+    if anzahl > 0 {
+        let (elo, fehler) = get_elo(siege, remis, niederlagen);
+        println!("A gegen B: +{siege} ={remis} -{niederlagen}, Elo {elo:+.1} ± {fehler:.1}");
+    }
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,70 @@
+use std::{env, fs, process::ExitCode};
+
+use dame::{
+    ai::{tuning, Gewichte},
+    pdn::PdnSpiel,
+};
+
+const ITERATIONEN: u32 = 2000;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (ausgabe, pdn_dateien) = match args.split_first() {
+        Some((ausgabe, pdn_dateien)) if !pdn_dateien.is_empty() => (ausgabe, pdn_dateien),
+        _ => {
+            eprintln!("Verwendung: dame-tuning <ausgabe> <spiele.pdn>...");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut spiele = Vec::new();
+    for datei in pdn_dateien {
+        let text = match fs::read_to_string(datei) {
+            Ok(text) => text,
+            Err(err) => {
+                eprintln!("{datei} konnte nicht gelesen werden: {err}");
+                return ExitCode::FAILURE;
+            }
+        };
+        match PdnSpiel::parse_alle(&text) {
+            Some(neue_spiele) => spiele.extend(neue_spiele),
+            None => {
+                eprintln!("{datei} enthält ungültiges PDN");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let stellungen = tuning::get_tuning_stellungen(&spiele);
+    if stellungen.is_empty() {
+        eprintln!("Die Spiele enthalten keine Stellungen mit Ergebnis");
+        return ExitCode::FAILURE;
+    }
+    println!(
+        "{} Spiele gelesen, {} Stellungen",
+        spiele.len(),
+        stellungen.len()
+    );
+
+    // Bisherige Gewichte aus der Ausgabedatei weiter verbessern
+    let start = Gewichte::load(ausgabe).unwrap_or_default();
+    let k = tuning::find_k(&stellungen, &start);
+    println!(
+        "k = {k:.3}, Fehler vorher {:.6}",
+        tuning::get_fehler(&stellungen, k, &start)
+    );
+    let gewichte = tuning::tune(&stellungen, k, &start, ITERATIONEN, |iteration, fehler| {
+        println!("Iteration {iteration}: Fehler {fehler:.6}");
+    });
+    println!(
+        "Fehler nachher {:.6}",
+        tuning::get_fehler(&stellungen, k, &gewichte)
+    );
+
+    if let Err(err) = fs::write(ausgabe, gewichte.to_text()) {
+        eprintln!("{ausgabe} konnte nicht geschrieben werden: {err}");
+        return ExitCode::FAILURE;
+    }
+    print!("{}", gewichte.to_text());
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,53 @@
+use super::{Position, SpielBrett, Spieler};
+
+const fn splitmix64(zustand: u64) -> (u64, u64) {
+    let zustand = zustand.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = zustand;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    (zustand, z ^ (z >> 31))
+}
+
+const ANZAHL_FELDER: usize = SpielBrett::SIZE * SpielBrett::SIZE / 2;
+
+// Zobrist-Schlüssel: für jedes Feld und jede Figur ein fester Zufallswert, damit der Hash
+// über Programmversionen hinweg stabil bleibt und in Dateien gespeichert werden kann.
+const ZOBRIST: ([[u64; 4]; ANZAHL_FELDER], u64) = {
+    let mut tabelle = [[0; 4]; ANZAHL_FELDER];
+    let mut zustand = 0x4441_4D45;
+    let mut feld = 0;
+    while feld < ANZAHL_FELDER {
+        let mut figur = 0;
+        while figur < 4 {
+            let (neuer_zustand, wert) = splitmix64(zustand);
+            zustand = neuer_zustand;
+            tabelle[feld][figur] = wert;
+            figur += 1;
+        }
+        feld += 1;
+    }
+    let (_, am_zug) = splitmix64(zustand);
+    (tabelle, am_zug)
+};
+
+impl SpielBrett {
+    pub fn get_hash(&self, am_zug: Spieler) -> u64 {
+        let (tabelle, am_zug_schluessel) = ZOBRIST;
+        let mut hash = match am_zug {
+            Spieler::Schwarz => 0,
+            Spieler::Weiss => am_zug_schluessel,
+        };
+        for zeile in 0..Self::SIZE {
+            for spalte in 0..Self::SIZE {
+                let position = Position { spalte, zeile };
+                if !position.valid() {
+                    continue;
+                }
+                if let Some(figur) = self.get(position).figur_index() {
+                    hash ^= tabelle[position.to_feld_nummer() - 1][figur];
+                }
+            }
+        }
+        hash
+    }
+}
@@ -1,32 +1,38 @@
 use std::{
-    default,
-    fmt::{self, Debug, Formatter},
+    fmt::{self, Debug, Display, Formatter},
     ops::Not,
 };
 
 mod position;
 pub use position::*;
+mod hash;
 mod moves;
+mod notation;
+pub use notation::*;
 mod parse;
+mod partie;
+pub use partie::*;
+mod pruefung;
+pub use pruefung::*;
 
 #[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
 pub enum Spieler {
-    Mensch,
-    Computer,
+    Schwarz,
+    Weiss,
 }
 
 impl Spieler {
     pub fn move_direction(self) -> RichtungVertikal {
         match self {
-            Self::Mensch => RichtungVertikal::Unten,
-            Self::Computer => RichtungVertikal::Oben,
+            Self::Schwarz => RichtungVertikal::Unten,
+            Self::Weiss => RichtungVertikal::Oben,
         }
     }
 
     pub fn dame_zeile(self) -> usize {
         match self {
-            Self::Mensch => SpielBrett::SIZE - 1,
-            Self::Computer => 0,
+            Self::Schwarz => SpielBrett::SIZE - 1,
+            Self::Weiss => 0,
         }
     }
 }
@@ -36,8 +42,8 @@ impl Not for Spieler {
 
     fn not(self) -> Self::Output {
         match self {
-            Self::Mensch => Self::Computer,
-            Self::Computer => Self::Mensch,
+            Self::Schwarz => Self::Weiss,
+            Self::Weiss => Self::Schwarz,
         }
     }
 }
@@ -51,13 +57,42 @@ pub enum Feld {
 }
 
 impl Feld {
+    pub const FIGUREN: [Feld; 4] = [
+        Feld::Stein(Spieler::Schwarz),
+        Feld::Dame(Spieler::Schwarz),
+        Feld::Stein(Spieler::Weiss),
+        Feld::Dame(Spieler::Weiss),
+    ];
+
+    /// Index in `Feld::FIGUREN`
+    pub fn figur_index(self) -> Option<usize> {
+        Some(match self {
+            Self::Leer => return None,
+            Self::Stein(Spieler::Schwarz) => 0,
+            Self::Dame(Spieler::Schwarz) => 1,
+            Self::Stein(Spieler::Weiss) => 2,
+            Self::Dame(Spieler::Weiss) => 3,
+        })
+    }
+
+    pub fn get_spieler(self) -> Option<Spieler> {
+        match self {
+            Self::Leer => None,
+            Self::Stein(spieler) | Self::Dame(spieler) => Some(spieler),
+        }
+    }
+
+    pub fn is_spieler(self, spieler: Spieler) -> bool {
+        self.get_spieler() == Some(spieler)
+    }
+
     pub fn to_character(self) -> char {
         match self {
             Self::Leer => '_',
-            Self::Stein(Spieler::Mensch) => 'm',
-            Self::Dame(Spieler::Mensch) => 'M',
-            Self::Stein(Spieler::Computer) => 'c',
-            Self::Dame(Spieler::Computer) => 'C',
+            Self::Stein(Spieler::Schwarz) => 's',
+            Self::Dame(Spieler::Schwarz) => 'S',
+            Self::Stein(Spieler::Weiss) => 'w',
+            Self::Dame(Spieler::Weiss) => 'W',
         }
     }
 }
@@ -108,6 +143,24 @@ pub struct SpielBrett {
 impl SpielBrett {
     pub const SIZE: usize = 8;
 
+    pub fn startaufstellung() -> Self {
+        let mut brett = Self::default();
+        for zeile in 0..Self::SIZE {
+            for spalte in 0..Self::SIZE {
+                let position = Position { spalte, zeile };
+                if !position.valid() {
+                    continue;
+                }
+                if zeile < 3 {
+                    brett.set(position, Feld::Stein(Spieler::Schwarz));
+                } else if zeile >= Self::SIZE - 3 {
+                    brett.set(position, Feld::Stein(Spieler::Weiss));
+                }
+            }
+        }
+        brett
+    }
+
     pub fn get(&self, pos: Position) -> Feld {
         if !pos.valid() {
             panic!("invalid position");
@@ -127,9 +180,11 @@ impl SpielBrett {
     pub fn has_won(&self, winner: Spieler) -> bool {
         for zeile in 0..SpielBrett::SIZE {
             for spalte in 0..SpielBrett::SIZE {
-                if let Feld::Stein(player) | Feld::Dame(player) =
-                    self.get(Position { zeile, spalte })
-                {
+                let position = Position { zeile, spalte };
+                if !position.valid() {
+                    continue;
+                }
+                if let Feld::Stein(player) | Feld::Dame(player) = self.get(position) {
                     if player == !winner {
                         return false;
                     }
@@ -137,10 +192,12 @@ impl SpielBrett {
             }
         }
 
-        return true;
+        true
     }
+}
 
-    pub fn to_string(&self) -> String {
+impl Display for SpielBrett {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         let mut result = String::new();
         for (zeile_index, zeile) in self.zeilen.into_iter().enumerate() {
             zeile.append_to_string(zeile_index, &mut result);
@@ -148,15 +205,12 @@ impl SpielBrett {
                 result.push('\n');
             }
         }
-        result
+        f.write_str(&result)
     }
 }
 
 impl Debug for SpielBrett {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        match f.write_str(&self.to_string()) {
-            Ok(_) => Ok(()),
-            Err(err) => Err(err),
-        }
+        Display::fmt(self, f)
     }
 }
@@ -280,6 +280,9 @@ impl SpielBrett {
                     if moves_len_before_append_following == moves.len() {
                         moves.insert(neues_brett);
                     }
+                    // Hinter der geschlagenen Figur darf in dieser Richtung keine weitere
+                    // geschlagen werden
+                    continue 'richtung_v;
                 }
             }
         }
@@ -357,7 +360,7 @@ mod tests {
             "_ _ _ _ "
             " c _ _ _"
 
-            possible moves for Spieler::Computer:
+            possible moves for Spieler::Weiss:
             "_ _ m _ "
             " _ _ M _"
             "_ _ _ _ "
@@ -388,7 +391,7 @@ mod tests {
             "_ _ _ _ "
             " _ _ _ _"
 
-            possible moves for Spieler::Computer:
+            possible moves for Spieler::Weiss:
             "_ _ _ _ "
             " _ _ _ _"
             "_ _ _ _ "
@@ -410,7 +413,7 @@ mod tests {
             "_ _ _ _ "
             " _ _ _ _"
 
-            possible moves for Spieler::Computer:
+            possible moves for Spieler::Weiss:
             "_ C _ _ "
             " _ _ _ _"
             "_ _ _ _ "
@@ -441,7 +444,7 @@ mod tests {
             "_ m _ _ "
             " _ _ _ c"
 
-            possible moves for Spieler::Mensch:
+            possible moves for Spieler::Schwarz:
             "_ _ _ _ "
             " m _ _ _"
             "_ _ _ _ "
@@ -483,7 +486,7 @@ mod tests {
             "_ m _ _ "
             " c _ _ _"
 
-            possible moves for Spieler::Computer:
+            possible moves for Spieler::Weiss:
             "_ _ _ _ "
             " _ _ _ _"
             "_ _ _ _ "
@@ -505,7 +508,7 @@ mod tests {
             "_ _ _ _ "
             " _ _ _ _"
 
-            possible moves for Spieler::Computer:
+            possible moves for Spieler::Weiss:
             "_ _ C _ "
             " _ _ _ _"
             "_ _ _ _ "
@@ -527,7 +530,7 @@ mod tests {
             "_ M M _ "
             " c c c c"
 
-            possible moves for Spieler::Computer:
+            possible moves for Spieler::Weiss:
             "_ _ _ _ "
             " _ _ _ _"
             "_ _ _ _ "
@@ -576,7 +579,7 @@ mod tests {
             "_ _ _ _ "
             " _ c _ _"
 
-            possible moves for Spieler::Mensch:
+            possible moves for Spieler::Schwarz:
             "_ _ _ _ "
             " _ _ _ _"
             "_ _ _ _ "
@@ -598,7 +601,7 @@ mod tests {
             "_ _ _ _ "
             " _ _ _ _"
 
-            possible moves for Spieler::Mensch:
+            possible moves for Spieler::Schwarz:
             "_ _ _ m "
             " _ _ _ _"
             "_ _ _ _ "
@@ -620,7 +623,7 @@ mod tests {
             "_ _ _ _ "
             " _ _ _ _"
 
-            possible moves for Spieler::Mensch:
+            possible moves for Spieler::Schwarz:
             "_ _ _ _ "
             " _ c c _"
             "_ _ _ _ "
@@ -651,7 +654,7 @@ mod tests {
             "_ _ _ _ "
             " _ _ _ _"
 
-            possible moves for Spieler::Computer:
+            possible moves for Spieler::Weiss:
             "_ C _ _ "
             " _ m _ _"
             "_ _ _ _ "
@@ -675,7 +678,7 @@ mod tests {
             "_ _ _ _ "
             " _ _ _ _"
 
-            possible moves for Spieler::Mensch:
+            possible moves for Spieler::Schwarz:
             "_ M _ _ "
             " _ _ _ _"
             "_ _ _ _ "
@@ -762,7 +765,7 @@ mod tests {
             "_ _ _ _ "
             " _ _ _ _"
 
-            possible moves for Spieler::Computer:
+            possible moves for Spieler::Weiss:
             "_ _ _ C "
             " _ _ _ _"
             "_ _ _ _ "
@@ -784,7 +787,7 @@ mod tests {
             "_ _ _ _ "
             " _ _ _ _"
 
-            possible moves for Spieler::Computer:
+            possible moves for Spieler::Weiss:
             "_ _ _ C "
             " _ _ _ _"
             "_ _ _ _ "
@@ -815,7 +818,7 @@ mod tests {
             "_ _ _ m "
             " _ _ _ _"
 
-            possible moves for Spieler::Computer:
+            possible moves for Spieler::Weiss:
             "_ _ _ _ "
             " _ _ _ _"
             "_ _ _ _ "
@@ -843,5 +846,27 @@ mod tests {
             "_ _ _ _ "
             " _ _ _ C"
         );
+
+        // Nur eine Figur pro Sprung, auch wenn dahinter in derselben Richtung noch eine steht
+        test_moves!(
+            "_ _ _ _ "
+            " _ _ _ _"
+            "_ _ _ C "
+            " _ _ _ _"
+            "_ _ _ _ "
+            " _ C _ _"
+            "_ _ _ _ "
+            " M _ _ _"
+
+            possible moves for Spieler::Schwarz:
+            "_ _ _ _ "
+            " _ _ _ M"
+            "_ _ _ _ "
+            " _ _ _ _"
+            "_ _ _ _ "
+            " _ _ _ _"
+            "_ _ _ _ "
+            " _ _ _ _"
+        );
     }
 }
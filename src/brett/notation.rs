@@ -0,0 +1,301 @@
+use super::{Feld, Position, RichtungHorizontal, RichtungVertikal, SpielBrett, Spieler};
+
+impl Position {
+    // Die dunklen Felder werden zeilenweise von oben links nach unten rechts durchnummeriert,
+    // beginnend mit 1. Die Steine von Schwarz stehen zu Beginn auf 1 bis 12.
+    pub fn to_feld_nummer(self) -> usize {
+        self.zeile * SpielBrett::SIZE / 2 + self.spalte / 2 + 1
+    }
+
+    pub fn from_feld_nummer(nummer: usize) -> Option<Self> {
+        if nummer == 0 || nummer > SpielBrett::SIZE * SpielBrett::SIZE / 2 {
+            return None;
+        }
+        let zeile = (nummer - 1) / (SpielBrett::SIZE / 2);
+        let spalte = (nummer - 1) % (SpielBrett::SIZE / 2) * 2 + zeile % 2;
+        Some(Self { spalte, zeile })
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Zug {
+    pub spieler: Spieler,
+    pub pfad: Vec<Position>,
+    pub geschlagen: Vec<Position>,
+}
+
+impl Zug {
+    pub fn von(&self) -> Position {
+        self.pfad[0]
+    }
+
+    pub fn nach(&self) -> Position {
+        self.pfad[self.pfad.len() - 1]
+    }
+
+    pub fn is_schlagen(&self) -> bool {
+        !self.geschlagen.is_empty()
+    }
+
+    pub fn to_notation(&self) -> String {
+        let trennzeichen = if self.is_schlagen() { "x" } else { "-" };
+        self.pfad
+            .iter()
+            .map(|position| position.to_feld_nummer().to_string())
+            .collect::<Vec<_>>()
+            .join(trennzeichen)
+    }
+}
+
+fn parse_notation(notation: &str) -> Option<Vec<Position>> {
+    let felder = notation
+        .trim()
+        .split(['-', 'x', 'X', ':'])
+        .map(|nummer| Position::from_feld_nummer(nummer.parse().ok()?))
+        .collect::<Option<Vec<_>>>()?;
+    if felder.len() < 2 {
+        return None;
+    }
+    Some(felder)
+}
+
+impl SpielBrett {
+    fn find_schlagen_pfad(
+        &self,
+        position: Position,
+        nach: Position,
+        zu_schlagen: &[Position],
+        pfad: &mut Vec<Position>,
+        geschlagen: &mut Vec<Position>,
+    ) -> bool {
+        if geschlagen.len() == zu_schlagen.len() {
+            return position == nach;
+        }
+
+        let (spieler, dame) = match self.get(position) {
+            Feld::Stein(spieler) => (spieler, false),
+            Feld::Dame(spieler) => (spieler, true),
+            Feld::Leer => return false,
+        };
+        for richtung_h in [RichtungHorizontal::Links, RichtungHorizontal::Rechts] {
+            for richtung_v in [RichtungVertikal::Oben, RichtungVertikal::Unten] {
+                if !dame && geschlagen.is_empty() && richtung_v != spieler.move_direction() {
+                    continue;
+                }
+
+                let max_distance = if dame { SpielBrett::SIZE } else { 1 };
+                let mut schlagen_position = None;
+                for number_of_fields in 1..=max_distance {
+                    let Some(neue_position) =
+                        position.offset(richtung_h, richtung_v, number_of_fields)
+                    else {
+                        break;
+                    };
+                    match self.get(neue_position) {
+                        Feld::Leer => continue,
+                        Feld::Stein(other) | Feld::Dame(other) if other != spieler => {
+                            schlagen_position = Some(neue_position);
+                        }
+                        Feld::Stein(_) | Feld::Dame(_) => (),
+                    }
+                    break;
+                }
+                let Some(schlagen_position) = schlagen_position else {
+                    continue;
+                };
+                if !zu_schlagen.contains(&schlagen_position) {
+                    continue;
+                }
+                let Some(neue_position) = schlagen_position.offset(richtung_h, richtung_v, 1)
+                else {
+                    continue;
+                };
+                if self.get(neue_position) != Feld::Leer {
+                    continue;
+                }
+
+                let umgewandelt = !dame && neue_position.zeile == spieler.dame_zeile();
+                let mut neues_brett = self.clone();
+                neues_brett.set(position, Feld::Leer);
+                neues_brett.set(schlagen_position, Feld::Leer);
+                neues_brett.set(
+                    neue_position,
+                    if dame || umgewandelt {
+                        Feld::Dame(spieler)
+                    } else {
+                        Feld::Stein(spieler)
+                    },
+                );
+
+                pfad.push(neue_position);
+                geschlagen.push(schlagen_position);
+                // Ein Stein, der zur Dame wird, beendet den Zug
+                let gefunden = if umgewandelt {
+                    geschlagen.len() == zu_schlagen.len() && neue_position == nach
+                } else {
+                    neues_brett.find_schlagen_pfad(
+                        neue_position,
+                        nach,
+                        zu_schlagen,
+                        pfad,
+                        geschlagen,
+                    )
+                };
+                if gefunden {
+                    return true;
+                }
+                pfad.pop();
+                geschlagen.pop();
+            }
+        }
+
+        false
+    }
+
+    /// Ermittelt, welcher Zug von `spieler` aus diesem Brett `nachher` macht.
+    pub fn get_zug(&self, nachher: &SpielBrett, spieler: Spieler) -> Option<Zug> {
+        let mut verlassen = Vec::new();
+        let mut erreicht = Vec::new();
+        let mut unveraendert = Vec::new();
+        let mut geschlagen = Vec::new();
+
+        for zeile in 0..Self::SIZE {
+            for spalte in 0..Self::SIZE {
+                let position = Position { spalte, zeile };
+                if !position.valid() {
+                    continue;
+                }
+                match (self.get(position), nachher.get(position)) {
+                    (Feld::Stein(vorher) | Feld::Dame(vorher), Feld::Leer) if vorher == spieler => {
+                        verlassen.push(position)
+                    }
+                    (Feld::Stein(vorher) | Feld::Dame(vorher), Feld::Leer) if vorher != spieler => {
+                        geschlagen.push(position)
+                    }
+                    (Feld::Leer, Feld::Stein(danach) | Feld::Dame(danach)) if danach == spieler => {
+                        erreicht.push(position)
+                    }
+                    (Feld::Stein(vorher) | Feld::Dame(vorher), danach)
+                        if vorher == spieler && danach == self.get(position) =>
+                    {
+                        unveraendert.push(position)
+                    }
+                    (vorher, danach) if vorher == danach => (),
+                    _ => return None,
+                }
+            }
+        }
+
+        let (kandidaten, nach) = match (verlassen.as_slice(), erreicht.as_slice()) {
+            ([von], [nach]) => (vec![*von], Some(*nach)),
+            ([], []) if !geschlagen.is_empty() => (unveraendert.clone(), None),
+            _ => return None,
+        };
+
+        if geschlagen.is_empty() {
+            return Some(Zug {
+                spieler,
+                pfad: vec![kandidaten[0], nach?],
+                geschlagen,
+            });
+        }
+
+        for von in kandidaten {
+            let nach = nach.unwrap_or(von);
+            let mut pfad = vec![von];
+            let mut reihenfolge = Vec::new();
+            if self.find_schlagen_pfad(von, nach, &geschlagen, &mut pfad, &mut reihenfolge) {
+                return Some(Zug {
+                    spieler,
+                    pfad,
+                    geschlagen: reihenfolge,
+                });
+            }
+        }
+
+        None
+    }
+
+    /// Alle möglichen Züge zusammen mit dem Brett danach, sortiert nach Notation.
+    pub fn get_possible_zuege(&self, spieler: Spieler) -> Vec<(Zug, SpielBrett)> {
+        let mut zuege: Vec<_> = self
+            .get_possible_moves(spieler)
+            .into_iter()
+            .filter_map(|brett| Some((self.get_zug(&brett, spieler)?, brett)))
+            .collect();
+        zuege.sort_by_key(|(zug, _)| {
+            zug.pfad
+                .iter()
+                .map(|position| position.to_feld_nummer())
+                .collect::<Vec<_>>()
+        });
+        zuege
+    }
+
+    /// Sucht den Zug zu einer Notation wie `11-15` oder `22x15x6`. Bei Schlagzügen reicht
+    /// es, Start- und Zielfeld anzugeben.
+    pub fn find_zug(&self, spieler: Spieler, notation: &str) -> Option<SpielBrett> {
+        let felder = parse_notation(notation)?;
+        self.get_possible_zuege(spieler)
+            .into_iter()
+            .find(|(zug, _)| {
+                zug.von() == felder[0]
+                    && zug.nach() == felder[felder.len() - 1]
+                    && (felder.len() == 2 || zug.pfad == felder)
+            })
+            .map(|(_, brett)| brett)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feld_nummer() {
+        for nummer in 1..=32 {
+            let position = Position::from_feld_nummer(nummer).unwrap();
+            assert!(position.valid());
+            assert_eq!(position.to_feld_nummer(), nummer);
+        }
+        assert_eq!(Position::from_feld_nummer(0), None);
+        assert_eq!(Position::from_feld_nummer(33), None);
+        assert_eq!(
+            Position::from_feld_nummer(5),
+            Some(Position {
+                spalte: 1,
+                zeile: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_zug_notation() {
+        let brett = SpielBrett::parse(concat!(
+            "_ m _ _ \n",
+            " _ c _ _\n",
+            "_ _ _ _ \n",
+            " _ _ c _\n",
+            "_ _ _ _ \n",
+            " _ _ c _\n",
+            "_ _ _ _ \n",
+            " _ c _ _",
+        ))
+        .unwrap();
+
+        let zuege = brett.get_possible_zuege(Spieler::Schwarz);
+        assert_eq!(zuege.len(), 1);
+        assert_eq!(zuege[0].0.to_notation(), "2x11x20x27");
+        assert_eq!(zuege[0].0.geschlagen.len(), 3);
+
+        assert_eq!(
+            brett.find_zug(Spieler::Schwarz, "2x27"),
+            Some(zuege[0].1.clone())
+        );
+        assert_eq!(brett.find_zug(Spieler::Schwarz, "2-7"), None);
+
+        let zuege = brett.get_possible_zuege(Spieler::Weiss);
+        assert!(zuege.iter().any(|(zug, _)| zug.to_notation() == "15-11"));
+        assert!(zuege.iter().all(|(zug, _)| !zug.is_schlagen()));
+    }
+}
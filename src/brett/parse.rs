@@ -4,10 +4,15 @@ impl Feld {
     fn parse(character: char) -> Option<Self> {
         Some(match character {
             '_' => Self::Leer,
-            'm' => Self::Stein(Spieler::Mensch),
-            'M' => Self::Dame(Spieler::Mensch),
-            'c' => Self::Stein(Spieler::Computer),
-            'C' => Self::Dame(Spieler::Computer),
+            's' => Self::Stein(Spieler::Schwarz),
+            'S' => Self::Dame(Spieler::Schwarz),
+            'w' => Self::Stein(Spieler::Weiss),
+            'W' => Self::Dame(Spieler::Weiss),
+            // Ältere Dateien benennen die Seiten nach Mensch und Computer
+            'm' => Self::Stein(Spieler::Schwarz),
+            'M' => Self::Dame(Spieler::Schwarz),
+            'c' => Self::Stein(Spieler::Weiss),
+            'C' => Self::Dame(Spieler::Weiss),
             _ => return None,
         })
     }
@@ -31,13 +36,7 @@ impl Zeile {
                 continue;
             }
 
-            result.set(
-                position,
-                match Feld::parse(character) {
-                    Some(feld) => feld,
-                    None => return None,
-                },
-            );
+            result.set(position, Feld::parse(character)?);
         }
 
         Some(result)
@@ -55,10 +54,7 @@ impl SpielBrett {
                 let mut zeilen = [Zeile::default(); Self::SIZE];
 
                 for (zeile_index, zeile_text) in text.lines().enumerate() {
-                    let zeile = match Zeile::parse(zeile_index, zeile_text) {
-                        Some(zeile) => zeile,
-                        None => return None,
-                    };
+                    let zeile = Zeile::parse(zeile_index, zeile_text)?;
 
                     zeilen[zeile_index] = zeile;
                 }
@@ -90,30 +86,30 @@ mod tests {
         assert_eq!(
             brett.zeilen[0],
             Zeile::from_felder([
-                Feld::Stein(Spieler::Mensch),
-                Feld::Stein(Spieler::Mensch),
-                Feld::Stein(Spieler::Mensch),
-                Feld::Stein(Spieler::Mensch)
+                Feld::Stein(Spieler::Schwarz),
+                Feld::Stein(Spieler::Schwarz),
+                Feld::Stein(Spieler::Schwarz),
+                Feld::Stein(Spieler::Schwarz)
             ])
         );
 
         assert_eq!(
             brett.zeilen[1],
             Zeile::from_felder([
-                Feld::Stein(Spieler::Mensch),
-                Feld::Stein(Spieler::Mensch),
-                Feld::Stein(Spieler::Mensch),
-                Feld::Stein(Spieler::Mensch)
+                Feld::Stein(Spieler::Schwarz),
+                Feld::Stein(Spieler::Schwarz),
+                Feld::Stein(Spieler::Schwarz),
+                Feld::Stein(Spieler::Schwarz)
             ])
         );
 
         assert_eq!(
             brett.zeilen[2],
             Zeile::from_felder([
-                Feld::Stein(Spieler::Mensch),
-                Feld::Stein(Spieler::Mensch),
-                Feld::Stein(Spieler::Mensch),
-                Feld::Stein(Spieler::Mensch)
+                Feld::Stein(Spieler::Schwarz),
+                Feld::Stein(Spieler::Schwarz),
+                Feld::Stein(Spieler::Schwarz),
+                Feld::Stein(Spieler::Schwarz)
             ])
         );
 
@@ -130,32 +126,34 @@ mod tests {
         assert_eq!(
             brett.zeilen[5],
             Zeile::from_felder([
-                Feld::Stein(Spieler::Computer),
-                Feld::Stein(Spieler::Computer),
-                Feld::Stein(Spieler::Computer),
-                Feld::Stein(Spieler::Computer)
+                Feld::Stein(Spieler::Weiss),
+                Feld::Stein(Spieler::Weiss),
+                Feld::Stein(Spieler::Weiss),
+                Feld::Stein(Spieler::Weiss)
             ])
         );
 
         assert_eq!(
             brett.zeilen[6],
             Zeile::from_felder([
-                Feld::Stein(Spieler::Computer),
-                Feld::Stein(Spieler::Computer),
-                Feld::Stein(Spieler::Computer),
-                Feld::Stein(Spieler::Computer)
+                Feld::Stein(Spieler::Weiss),
+                Feld::Stein(Spieler::Weiss),
+                Feld::Stein(Spieler::Weiss),
+                Feld::Stein(Spieler::Weiss)
             ])
         );
 
         assert_eq!(
             brett.zeilen[7],
             Zeile::from_felder([
-                Feld::Stein(Spieler::Computer),
-                Feld::Stein(Spieler::Computer),
-                Feld::Stein(Spieler::Computer),
-                Feld::Stein(Spieler::Computer)
+                Feld::Stein(Spieler::Weiss),
+                Feld::Stein(Spieler::Weiss),
+                Feld::Stein(Spieler::Weiss),
+                Feld::Stein(Spieler::Weiss)
             ])
         );
+
+        assert_eq!(brett, SpielBrett::startaufstellung());
     }
 
     #[test]
@@ -175,10 +173,10 @@ mod tests {
         assert_eq!(
             brett.zeilen[0],
             Zeile::from_felder([
-                Feld::Dame(Spieler::Computer),
-                Feld::Stein(Spieler::Computer),
+                Feld::Dame(Spieler::Weiss),
+                Feld::Stein(Spieler::Weiss),
                 Feld::Leer,
-                Feld::Stein(Spieler::Mensch)
+                Feld::Stein(Spieler::Schwarz)
             ])
         );
 
@@ -186,10 +184,37 @@ mod tests {
             brett.zeilen[1],
             Zeile::from_felder([
                 Feld::Leer,
-                Feld::Dame(Spieler::Mensch),
-                Feld::Stein(Spieler::Computer),
-                Feld::Dame(Spieler::Computer)
+                Feld::Dame(Spieler::Schwarz),
+                Feld::Stein(Spieler::Weiss),
+                Feld::Dame(Spieler::Weiss)
             ])
         );
     }
+
+    #[test]
+    fn test_brett_parse_farben() {
+        let text = concat!(
+            "W w _ s \n",
+            " _ S w W\n",
+            "_ _ _ _ \n",
+            " _ _ _ _\n",
+            "_ _ _ _ \n",
+            " _ _ _ _\n",
+            "_ _ _ _ \n",
+            " _ _ _ _",
+        );
+        let brett = SpielBrett::parse(text).unwrap();
+        assert_eq!(brett.to_string(), text);
+        // Dateien mit m und c für Mensch und Computer bleiben lesbar
+        assert_eq!(
+            SpielBrett::parse(
+                &text
+                    .replace('s', "m")
+                    .replace('S', "M")
+                    .replace('w', "c")
+                    .replace('W', "C")
+            ),
+            Some(brett)
+        );
+    }
 }
@@ -0,0 +1,302 @@
+use super::{Feld, Position, SpielBrett, Spieler};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Remisregeln {
+    /// Wie oft sich dieselbe Stellung mit demselben Spieler am Zug wiederholen darf
+    pub wiederholungen: usize,
+    /// Züge je Spieler ohne Schlagen und ohne Zug eines Steins, nach denen das Spiel
+    /// unentschieden endet
+    pub max_zuege_ohne_fortschritt: Option<u32>,
+    /// Begrenzung von Endspielen mit einer einzelnen Dame gegen wenige Figuren mit Dame
+    pub damen_endspiel: bool,
+}
+
+impl Default for Remisregeln {
+    fn default() -> Self {
+        Self {
+            wiederholungen: 3,
+            max_zuege_ohne_fortschritt: Some(25),
+            damen_endspiel: true,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum RemisGrund {
+    Wiederholung,
+    OhneFortschritt,
+    DamenEndspiel,
+    /// Beide Spieler haben sich auf Remis geeinigt
+    Vereinbart,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Spielstand {
+    Laeuft,
+    Gewonnen(Spieler),
+    Remis(RemisGrund),
+}
+
+/// Ein Zug ist unumkehrbar, wenn er schlägt oder einen Stein bewegt. Danach kann sich keine
+/// frühere Stellung mehr wiederholen.
+pub fn is_unumkehrbar(vorher: &SpielBrett, nachher: &SpielBrett) -> bool {
+    let mut figuren_vorher = 0;
+    let mut figuren_nachher = 0;
+    for zeile in 0..SpielBrett::SIZE {
+        for spalte in 0..SpielBrett::SIZE {
+            let position = Position { spalte, zeile };
+            if !position.valid() {
+                continue;
+            }
+            let feld_vorher = vorher.get(position);
+            let feld_nachher = nachher.get(position);
+            if matches!(feld_vorher, Feld::Stein(_)) && feld_vorher != feld_nachher {
+                return true;
+            }
+            figuren_vorher += usize::from(feld_vorher != Feld::Leer);
+            figuren_nachher += usize::from(feld_nachher != Feld::Leer);
+        }
+    }
+    figuren_vorher != figuren_nachher
+}
+
+fn count_figuren(brett: &SpielBrett, spieler: Spieler) -> (u32, u32) {
+    let statistik = brett.get_statistik();
+    let statistik = match spieler {
+        Spieler::Schwarz => statistik.schwarz,
+        Spieler::Weiss => statistik.weiss,
+    };
+    (statistik.steine, statistik.damen)
+}
+
+/// Halbzüge, die in einem Endspiel mit einer einzelnen Dame noch gespielt werden dürfen.
+/// Gegen zwei Figuren sind es 5 Züge je Spieler, gegen drei Figuren 16 Züge.
+pub(crate) fn get_damen_endspiel_limit(brett: &SpielBrett) -> Option<u32> {
+    for spieler in [Spieler::Schwarz, Spieler::Weiss] {
+        if count_figuren(brett, spieler) != (0, 1) {
+            continue;
+        }
+        let (steine, damen) = count_figuren(brett, !spieler);
+        if damen == 0 {
+            continue;
+        }
+        return match steine + damen {
+            1 | 2 => Some(10),
+            3 => Some(32),
+            _ => None,
+        };
+    }
+    None
+}
+
+/// Die Halbzüge im Endspiel mit einer einzelnen Dame nach dem Zug von `vorher` nach
+/// `nachher`, wenn es vorher `bisher` waren. Schlagen oder eine neue Dame beginnen neu.
+pub(crate) fn zaehle_damen_endspiel(vorher: &SpielBrett, nachher: &SpielBrett, bisher: u32) -> u32 {
+    if get_damen_endspiel_limit(nachher).is_some() && get_statistik_gleich(vorher, nachher) {
+        bisher + 1
+    } else {
+        0
+    }
+}
+
+/// Ob ein Endspiel mit einer einzelnen Dame nach `halbzuege` Halbzügen unentschieden ist
+pub(crate) fn is_damen_endspiel_remis(brett: &SpielBrett, halbzuege: u32) -> bool {
+    get_damen_endspiel_limit(brett).is_some_and(|limit| halbzuege >= limit)
+}
+
+/// Eine laufende Partie mit allem, was für die Remisregeln nötig ist.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Partie {
+    brett: SpielBrett,
+    am_zug: Spieler,
+    regeln: Remisregeln,
+    /// Hashes aller Stellungen seit dem letzten unumkehrbaren Zug, einschließlich der aktuellen
+    verlauf: Vec<u64>,
+    halbzuege_ohne_fortschritt: u32,
+    halbzuege_damen_endspiel: u32,
+}
+
+impl Partie {
+    pub fn new(brett: SpielBrett, am_zug: Spieler, regeln: Remisregeln) -> Self {
+        Self {
+            verlauf: vec![brett.get_hash(am_zug)],
+            brett,
+            am_zug,
+            regeln,
+            halbzuege_ohne_fortschritt: 0,
+            halbzuege_damen_endspiel: 0,
+        }
+    }
+
+    pub fn get_brett(&self) -> &SpielBrett {
+        &self.brett
+    }
+
+    pub fn get_am_zug(&self) -> Spieler {
+        self.am_zug
+    }
+
+    pub fn get_regeln(&self) -> Remisregeln {
+        self.regeln
+    }
+
+    pub fn get_halbzuege_ohne_fortschritt(&self) -> u32 {
+        self.halbzuege_ohne_fortschritt
+    }
+
+    pub(crate) fn get_halbzuege_damen_endspiel(&self) -> u32 {
+        self.halbzuege_damen_endspiel
+    }
+
+    pub(crate) fn get_verlauf(&self) -> &[u64] {
+        &self.verlauf
+    }
+
+    /// Führt einen Zug von `get_am_zug` aus. Gibt `None` zurück, wenn der Zug nicht erlaubt
+    /// ist oder das Spiel schon vorbei ist.
+    pub fn ziehe(&mut self, nachher: SpielBrett) -> Option<()> {
+        if self.get_spielstand() != Spielstand::Laeuft {
+            return None;
+        }
+        if !self
+            .brett
+            .get_possible_moves(self.am_zug)
+            .contains(&nachher)
+        {
+            return None;
+        }
+
+        if is_unumkehrbar(&self.brett, &nachher) {
+            self.halbzuege_ohne_fortschritt = 0;
+            self.verlauf.clear();
+        } else {
+            self.halbzuege_ohne_fortschritt += 1;
+        }
+        self.halbzuege_damen_endspiel =
+            zaehle_damen_endspiel(&self.brett, &nachher, self.halbzuege_damen_endspiel);
+
+        self.brett = nachher;
+        self.am_zug = !self.am_zug;
+        self.verlauf.push(self.brett.get_hash(self.am_zug));
+        Some(())
+    }
+
+    pub fn get_spielstand(&self) -> Spielstand {
+        if self.brett.has_won(!self.am_zug) || self.brett.get_possible_moves(self.am_zug).is_empty()
+        {
+            return Spielstand::Gewonnen(!self.am_zug);
+        }
+
+        let aktuell = self.brett.get_hash(self.am_zug);
+        let wiederholungen = self.verlauf.iter().filter(|&&hash| hash == aktuell).count();
+        if wiederholungen >= self.regeln.wiederholungen {
+            return Spielstand::Remis(RemisGrund::Wiederholung);
+        }
+
+        if let Some(max_zuege) = self.regeln.max_zuege_ohne_fortschritt {
+            if self.halbzuege_ohne_fortschritt >= max_zuege * 2 {
+                return Spielstand::Remis(RemisGrund::OhneFortschritt);
+            }
+        }
+
+        if self.regeln.damen_endspiel
+            && is_damen_endspiel_remis(&self.brett, self.halbzuege_damen_endspiel)
+        {
+            return Spielstand::Remis(RemisGrund::DamenEndspiel);
+        }
+
+        Spielstand::Laeuft
+    }
+}
+
+fn get_statistik_gleich(vorher: &SpielBrett, nachher: &SpielBrett) -> bool {
+    [Spieler::Schwarz, Spieler::Weiss]
+        .into_iter()
+        .all(|spieler| count_figuren(vorher, spieler) == count_figuren(nachher, spieler))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn damen_brett() -> SpielBrett {
+        SpielBrett::parse(concat!(
+            "_ _ _ M \n",
+            " _ _ _ _\n",
+            "_ _ _ _ \n",
+            " _ _ _ _\n",
+            "_ _ _ _ \n",
+            " _ _ _ _\n",
+            "_ _ _ _ \n",
+            " C _ _ _",
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_wiederholung() {
+        let regeln = Remisregeln {
+            damen_endspiel: false,
+            ..Remisregeln::default()
+        };
+        let mut partie = Partie::new(damen_brett(), Spieler::Weiss, regeln);
+        for _ in 0..2 {
+            for (am_zug, zug) in [
+                (Spieler::Weiss, "29-25"),
+                (Spieler::Schwarz, "4-8"),
+                (Spieler::Weiss, "25-29"),
+                (Spieler::Schwarz, "8-4"),
+            ] {
+                assert_eq!(partie.get_spielstand(), Spielstand::Laeuft);
+                let nachher = partie.get_brett().find_zug(am_zug, zug).unwrap();
+                partie.ziehe(nachher).unwrap();
+            }
+        }
+        assert_eq!(
+            partie.get_spielstand(),
+            Spielstand::Remis(RemisGrund::Wiederholung)
+        );
+        assert_eq!(partie.get_halbzuege_ohne_fortschritt(), 8);
+    }
+
+    #[test]
+    fn test_damen_endspiel() {
+        let mut partie = Partie::new(damen_brett(), Spieler::Weiss, Remisregeln::default());
+        let mut halbzuege = 0;
+        while partie.get_spielstand() == Spielstand::Laeuft {
+            let am_zug = partie.get_am_zug();
+            // Weder schlagen noch geschlagen werden und keine Stellung wiederholen
+            let (_, nachher) = partie
+                .get_brett()
+                .get_possible_zuege(am_zug)
+                .into_iter()
+                .find(|(zug, brett)| {
+                    !zug.is_schlagen()
+                        && !partie.get_verlauf().contains(&brett.get_hash(!am_zug))
+                        && brett
+                            .get_possible_zuege(!am_zug)
+                            .iter()
+                            .all(|(zug, _)| !zug.is_schlagen())
+                })
+                .unwrap();
+            partie.ziehe(nachher).unwrap();
+            halbzuege += 1;
+        }
+        assert_eq!(
+            partie.get_spielstand(),
+            Spielstand::Remis(RemisGrund::DamenEndspiel)
+        );
+        assert_eq!(halbzuege, 10);
+    }
+
+    #[test]
+    fn test_unumkehrbar() {
+        let brett = SpielBrett::startaufstellung();
+        let nachher = brett.find_zug(Spieler::Schwarz, "10-14").unwrap();
+        assert!(is_unumkehrbar(&brett, &nachher));
+
+        let brett = damen_brett();
+        let nachher = brett.find_zug(Spieler::Weiss, "29-25").unwrap();
+        assert!(!is_unumkehrbar(&brett, &nachher));
+    }
+}
@@ -50,6 +50,23 @@ impl Position {
             && self.zeile < SpielBrett::SIZE
             && self.spalte % 2 == self.zeile % 2
     }
+
+    pub fn offset(
+        self,
+        richtung_h: RichtungHorizontal,
+        richtung_v: RichtungVertikal,
+        number_of_fields: usize,
+    ) -> Option<Self> {
+        let spalte = self.spalte as isize + richtung_h.offset() * number_of_fields as isize;
+        let zeile = self.zeile as isize + richtung_v.offset() * number_of_fields as isize;
+        if !Self::valid_spalte(spalte) || !Self::valid_zeile(zeile) {
+            return None;
+        }
+        Some(Self {
+            spalte: spalte as usize,
+            zeile: zeile as usize,
+        })
+    }
 }
 
 #[cfg(test)]
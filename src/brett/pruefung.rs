@@ -0,0 +1,124 @@
+use std::fmt::{self, Display, Formatter};
+
+use super::{Feld, Position, SpielBrett, Spieler};
+
+/// So viele Figuren hat jeder Spieler in der Startaufstellung
+pub const MAX_FIGUREN: u32 = 12;
+
+/// Ein Grund, warum eine aufgebaute Stellung in keinem Spiel vorkommen kann
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Aufstellungsfehler {
+    /// Ein Stein auf der Reihe, auf der er zur Dame geworden wäre
+    SteinAufDamenReihe(Position),
+    ZuVieleFiguren(Spieler, u32),
+    KeineFiguren(Spieler),
+    KeinZug(Spieler),
+}
+
+impl Display for Aufstellungsfehler {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::SteinAufDamenReihe(position) => {
+                write!(f, "Stein auf Damenreihe: {}", position.to_feld_nummer())
+            }
+            Self::ZuVieleFiguren(spieler, anzahl) => {
+                write!(f, "{spieler:?} hat {anzahl} Figuren")
+            }
+            Self::KeineFiguren(spieler) => write!(f, "{spieler:?} hat keine Figuren"),
+            Self::KeinZug(spieler) => write!(f, "{spieler:?} kann nicht ziehen"),
+        }
+    }
+}
+
+impl SpielBrett {
+    /// Prüft, ob die Stellung mit `am_zug` als Spieler am Zug in einer Partie entstehen kann
+    pub fn pruefe_aufstellung(&self, am_zug: Spieler) -> Vec<Aufstellungsfehler> {
+        let mut fehler = Vec::new();
+        for zeile in 0..Self::SIZE {
+            for spalte in 0..Self::SIZE {
+                let position = Position { spalte, zeile };
+                if !position.valid() {
+                    continue;
+                }
+                if let Feld::Stein(spieler) = self.get(position) {
+                    if zeile == spieler.dame_zeile() {
+                        fehler.push(Aufstellungsfehler::SteinAufDamenReihe(position));
+                    }
+                }
+            }
+        }
+
+        let statistik = self.get_statistik();
+        for (spieler, statistik) in [
+            (Spieler::Schwarz, statistik.schwarz),
+            (Spieler::Weiss, statistik.weiss),
+        ] {
+            let anzahl = statistik.steine + statistik.damen;
+            if anzahl == 0 {
+                fehler.push(Aufstellungsfehler::KeineFiguren(spieler));
+            } else if anzahl > MAX_FIGUREN {
+                fehler.push(Aufstellungsfehler::ZuVieleFiguren(spieler, anzahl));
+            }
+        }
+
+        // Ohne Figuren ist schon gemeldet, dass das Spiel vorbei ist
+        let hat_figuren = !fehler.contains(&Aufstellungsfehler::KeineFiguren(am_zug));
+        if hat_figuren && self.get_possible_zuege(am_zug).is_empty() {
+            fehler.push(Aufstellungsfehler::KeinZug(am_zug));
+        }
+        fehler
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pruefe_aufstellung() {
+        let start = SpielBrett::startaufstellung();
+        assert_eq!(start.pruefe_aufstellung(Spieler::Schwarz), Vec::new());
+        assert_eq!(start.pruefe_aufstellung(Spieler::Weiss), Vec::new());
+
+        let brett = SpielBrett::parse(concat!(
+            "_ _ _ c \n",
+            " _ _ _ _\n",
+            "_ _ _ _ \n",
+            " _ _ _ _\n",
+            "_ _ _ _ \n",
+            " _ _ _ _\n",
+            "_ _ _ _ \n",
+            " _ _ _ m",
+        ))
+        .unwrap();
+        let fehler = brett.pruefe_aufstellung(Spieler::Schwarz);
+        assert_eq!(
+            fehler,
+            vec![
+                Aufstellungsfehler::SteinAufDamenReihe(Position {
+                    spalte: 6,
+                    zeile: 0
+                }),
+                Aufstellungsfehler::SteinAufDamenReihe(Position {
+                    spalte: 7,
+                    zeile: 7
+                }),
+                Aufstellungsfehler::KeinZug(Spieler::Schwarz),
+            ]
+        );
+        assert_eq!(fehler[0].to_string(), "Stein auf Damenreihe: 4");
+
+        let mut brett = start.clone();
+        brett.set(
+            Position {
+                spalte: 0,
+                zeile: 4,
+            },
+            Feld::Dame(Spieler::Weiss),
+        );
+        assert_eq!(
+            brett.pruefe_aufstellung(Spieler::Schwarz),
+            vec![Aufstellungsfehler::ZuVieleFiguren(Spieler::Weiss, 13)]
+        );
+    }
+}
@@ -0,0 +1,752 @@
+use std::{
+    io::{self, BufRead, BufReader, ErrorKind, Write},
+    net::TcpStream,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    ai::{Endspieldatenbank, Eroeffnungsbuch, SuchEinstellungen},
+    brett::{Feld, Partie, Position, Remisregeln, SpielBrett, Spieler, Spielstand},
+    pdn::Ergebnis,
+    uhr::{Uhr, Zeitkontrolle},
+};
+
+// DamExchange ist für internationales Dame mit 50 Feldern gedacht. Hier werden dieselben
+// Nachrichten mit den 32 Feldern unseres Bretts in PDN-Nummerierung verschickt, Schwarz (Z
+// für zwart) steht wie in PDN oben.
+
+pub const STANDARD_PORT: u16 = 27531;
+pub const DXP_VERSION: u32 = 1;
+/// So viele Zeichen hat ein Name, kürzere werden mit Leerzeichen aufgefüllt
+const NAME_LAENGE: usize = 32;
+const FELDER: usize = SpielBrett::SIZE * SpielBrett::SIZE / 2;
+
+fn spieler_to_farbe(spieler: Spieler) -> char {
+    match spieler {
+        Spieler::Schwarz => 'Z',
+        Spieler::Weiss => 'W',
+    }
+}
+
+fn farbe_to_spieler(farbe: char) -> Option<Spieler> {
+    match farbe {
+        'Z' => Some(Spieler::Schwarz),
+        'W' => Some(Spieler::Weiss),
+        _ => None,
+    }
+}
+
+fn feld_to_zeichen(feld: Feld) -> char {
+    match feld {
+        Feld::Leer => 'e',
+        Feld::Stein(Spieler::Schwarz) => 'z',
+        Feld::Stein(Spieler::Weiss) => 'w',
+        Feld::Dame(Spieler::Schwarz) => 'Z',
+        Feld::Dame(Spieler::Weiss) => 'W',
+    }
+}
+
+fn zeichen_to_feld(zeichen: char) -> Option<Feld> {
+    Some(match zeichen {
+        'e' => Feld::Leer,
+        'z' => Feld::Stein(Spieler::Schwarz),
+        'w' => Feld::Stein(Spieler::Weiss),
+        'Z' => Feld::Dame(Spieler::Schwarz),
+        'W' => Feld::Dame(Spieler::Weiss),
+        _ => return None,
+    })
+}
+
+/// Der Name mit Leerzeichen auf `NAME_LAENGE` gebracht, ohne Zeichen außerhalb von ASCII
+fn format_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|zeichen| if zeichen.is_ascii() { zeichen } else { '?' })
+        .take(NAME_LAENGE)
+        .collect();
+    format!("{name:NAME_LAENGE$}")
+}
+
+/// Liest eine Zahl mit fester Breite ab `start`
+fn parse_zahl(text: &str, start: usize, laenge: usize) -> Option<u32> {
+    text.get(start..start + laenge)?.parse().ok()
+}
+
+/// Die Antwort auf eine Spielanfrage
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Annahme {
+    Angenommen,
+    NichtMitDieserFarbe,
+    NichtMitDieserZeit,
+    NichtMitDieserStellung,
+    Abgelehnt,
+}
+
+impl Annahme {
+    fn to_code(self) -> char {
+        match self {
+            Self::Angenommen => '0',
+            Self::NichtMitDieserFarbe => '1',
+            Self::NichtMitDieserZeit => '2',
+            Self::NichtMitDieserStellung => '3',
+            Self::Abgelehnt => '9',
+        }
+    }
+
+    fn from_code(code: char) -> Self {
+        match code {
+            '0' => Self::Angenommen,
+            '1' => Self::NichtMitDieserFarbe,
+            '2' => Self::NichtMitDieserZeit,
+            '3' => Self::NichtMitDieserStellung,
+            _ => Self::Abgelehnt,
+        }
+    }
+}
+
+/// Warum eine Partie endet, aus Sicht dessen, der `GAMEEND` schickt
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Endgrund {
+    Unbekannt,
+    IchVerliere,
+    Remis,
+    IchGewinne,
+}
+
+impl Endgrund {
+    /// Das Ergebnis, wenn `absender` die Nachricht geschickt hat
+    pub fn to_ergebnis(self, absender: Spieler) -> Ergebnis {
+        match self {
+            Self::Unbekannt => Ergebnis::Offen,
+            Self::IchVerliere => Ergebnis::Sieg(!absender),
+            Self::Remis => Ergebnis::Unentschieden,
+            Self::IchGewinne => Ergebnis::Sieg(absender),
+        }
+    }
+
+    fn from_spielstand(spielstand: Spielstand, absender: Spieler) -> Self {
+        match spielstand {
+            Spielstand::Laeuft => Self::Unbekannt,
+            Spielstand::Gewonnen(spieler) if spieler == absender => Self::IchGewinne,
+            Spielstand::Gewonnen(_) => Self::IchVerliere,
+            Spielstand::Remis(_) => Self::Remis,
+        }
+    }
+}
+
+/// Eine Einladung zu einer Partie. Der Einladende heißt in DXP Initiator, der Eingeladene
+/// Follower.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Spielanfrage {
+    pub name: String,
+    pub follower_farbe: Spieler,
+    /// Die Bedenkzeit für `zuege` Züge, wird in ganzen Minuten verschickt
+    pub bedenkzeit: Duration,
+    pub zuege: u32,
+    /// Ohne Stellung beginnt die Partie aus der Startaufstellung
+    pub start: Option<(SpielBrett, Spieler)>,
+}
+
+impl Spielanfrage {
+    pub fn get_start(&self) -> (SpielBrett, Spieler) {
+        self.start
+            .clone()
+            .unwrap_or((SpielBrett::startaufstellung(), Spieler::Schwarz))
+    }
+}
+
+/// Ein Zug, wie ihn DXP beschreibt: mit Start, Ziel und den geschlagenen Feldern, aber ohne
+/// die Felder dazwischen
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct DxpZug {
+    /// Die Bedenkzeit für den Zug in ganzen Sekunden
+    pub zeit: Duration,
+    pub von: usize,
+    pub nach: usize,
+    pub geschlagen: Vec<usize>,
+}
+
+impl DxpZug {
+    /// Beschreibt den Zug, der von `brett` zu `nachher` führt
+    pub fn new(
+        brett: &SpielBrett,
+        nachher: &SpielBrett,
+        spieler: Spieler,
+        zeit: Duration,
+    ) -> Option<Self> {
+        let zug = brett.get_zug(nachher, spieler)?;
+        Some(Self {
+            zeit,
+            von: zug.von().to_feld_nummer(),
+            nach: zug.nach().to_feld_nummer(),
+            geschlagen: zug
+                .geschlagen
+                .iter()
+                .map(|position| position.to_feld_nummer())
+                .collect(),
+        })
+    }
+
+    /// Sucht das Brett nach dem Zug unter den erlaubten Zügen von `spieler`
+    pub fn find(&self, brett: &SpielBrett, spieler: Spieler) -> Option<SpielBrett> {
+        let mut geschlagen = self.geschlagen.clone();
+        geschlagen.sort_unstable();
+        brett
+            .get_possible_zuege(spieler)
+            .into_iter()
+            .find(|(zug, _)| {
+                let mut zug_geschlagen: Vec<_> = zug
+                    .geschlagen
+                    .iter()
+                    .map(|position| position.to_feld_nummer())
+                    .collect();
+                zug_geschlagen.sort_unstable();
+                zug.von().to_feld_nummer() == self.von
+                    && zug.nach().to_feld_nummer() == self.nach
+                    && zug_geschlagen == geschlagen
+            })
+            .map(|(_, nachher)| nachher)
+    }
+}
+
+/// Eine Nachricht von DamExchange. Jede Nachricht beginnt mit einem Buchstaben für ihre Art,
+/// gefolgt von Feldern fester Breite, und endet mit einem Nullbyte.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum DxpNachricht {
+    /// CHAT
+    Chat(String),
+    /// GAMEREQ
+    Spielanfrage(Spielanfrage),
+    /// GAMEACC
+    Spielannahme { name: String, annahme: Annahme },
+    /// MOVE
+    Zug(DxpZug),
+    /// GAMEEND, `weiter` ist wahr, wenn der Absender danach noch eine Partie spielen will
+    Spielende { grund: Endgrund, weiter: bool },
+    /// BACKREQ, zurück zur Stellung vor dem Zug `zug_nummer` mit `am_zug` am Zug
+    Zuruecknahme { zug_nummer: u32, am_zug: Spieler },
+    /// BACKACC
+    ZuruecknahmeAntwort { angenommen: bool },
+}
+
+impl DxpNachricht {
+    pub fn to_text(&self) -> String {
+        match self {
+            Self::Chat(text) => format!("C{text}"),
+            Self::Spielanfrage(anfrage) => {
+                let mut text = format!(
+                    "R{:02}{}{}{:03}{:03}",
+                    DXP_VERSION,
+                    format_name(&anfrage.name),
+                    spieler_to_farbe(anfrage.follower_farbe),
+                    anfrage.bedenkzeit.as_secs() / 60,
+                    anfrage.zuege,
+                );
+                match &anfrage.start {
+                    None => text.push('A'),
+                    Some((brett, am_zug)) => {
+                        text.push('B');
+                        text.push(spieler_to_farbe(*am_zug));
+                        for nummer in 1..=FELDER {
+                            let position = Position::from_feld_nummer(nummer).unwrap();
+                            text.push(feld_to_zeichen(brett.get(position)));
+                        }
+                    }
+                }
+                text
+            }
+            Self::Spielannahme { name, annahme } => {
+                format!("A{}{}", format_name(name), annahme.to_code())
+            }
+            Self::Zug(zug) => {
+                let mut text = format!(
+                    "M{:04}{:02}{:02}{:02}",
+                    zug.zeit.as_secs().min(9999),
+                    zug.von,
+                    zug.nach,
+                    zug.geschlagen.len()
+                );
+                for feld in &zug.geschlagen {
+                    text.push_str(&format!("{feld:02}"));
+                }
+                text
+            }
+            Self::Spielende { grund, weiter } => {
+                let grund = match grund {
+                    Endgrund::Unbekannt => 0,
+                    Endgrund::IchVerliere => 1,
+                    Endgrund::Remis => 2,
+                    Endgrund::IchGewinne => 3,
+                };
+                format!("E{grund}{}", if *weiter { 0 } else { 1 })
+            }
+            Self::Zuruecknahme { zug_nummer, am_zug } => {
+                format!("B{zug_nummer:03}{}", spieler_to_farbe(*am_zug))
+            }
+            Self::ZuruecknahmeAntwort { angenommen } => {
+                format!("K{}", if *angenommen { 0 } else { 2 })
+            }
+        }
+    }
+
+    pub fn parse(text: &str) -> Option<Self> {
+        let rest = text.get(1..)?;
+        Some(match text.chars().next()? {
+            'C' => Self::Chat(rest.to_string()),
+            'R' => {
+                let name = rest.get(2..2 + NAME_LAENGE)?.trim_end().to_string();
+                let rest = &rest[2 + NAME_LAENGE..];
+                let follower_farbe = farbe_to_spieler(rest.chars().next()?)?;
+                let bedenkzeit = Duration::from_secs(60 * u64::from(parse_zahl(rest, 1, 3)?));
+                let zuege = parse_zahl(rest, 4, 3)?;
+                let start = match rest.get(7..8)? {
+                    "A" => None,
+                    "B" => {
+                        let am_zug = farbe_to_spieler(rest[8..].chars().next()?)?;
+                        let felder = rest.get(9..9 + FELDER)?;
+                        let mut brett = SpielBrett::default();
+                        for (index, zeichen) in felder.chars().enumerate() {
+                            let position = Position::from_feld_nummer(index + 1)?;
+                            brett.set(position, zeichen_to_feld(zeichen)?);
+                        }
+                        Some((brett, am_zug))
+                    }
+                    _ => return None,
+                };
+                Self::Spielanfrage(Spielanfrage {
+                    name,
+                    follower_farbe,
+                    bedenkzeit,
+                    zuege,
+                    start,
+                })
+            }
+            'A' => Self::Spielannahme {
+                name: rest.get(..NAME_LAENGE)?.trim_end().to_string(),
+                annahme: Annahme::from_code(rest[NAME_LAENGE..].chars().next()?),
+            },
+            'M' => {
+                let anzahl = parse_zahl(rest, 8, 2)? as usize;
+                Self::Zug(DxpZug {
+                    zeit: Duration::from_secs(u64::from(parse_zahl(rest, 0, 4)?)),
+                    von: parse_zahl(rest, 4, 2)? as usize,
+                    nach: parse_zahl(rest, 6, 2)? as usize,
+                    geschlagen: (0..anzahl)
+                        .map(|index| Some(parse_zahl(rest, 10 + 2 * index, 2)? as usize))
+                        .collect::<Option<_>>()?,
+                })
+            }
+            'E' => Self::Spielende {
+                grund: match parse_zahl(rest, 0, 1)? {
+                    1 => Endgrund::IchVerliere,
+                    2 => Endgrund::Remis,
+                    3 => Endgrund::IchGewinne,
+                    _ => Endgrund::Unbekannt,
+                },
+                weiter: parse_zahl(rest, 1, 1)? == 0,
+            },
+            'B' => Self::Zuruecknahme {
+                zug_nummer: parse_zahl(rest, 0, 3)?,
+                am_zug: farbe_to_spieler(rest[3..].chars().next()?)?,
+            },
+            'K' => Self::ZuruecknahmeAntwort {
+                angenommen: rest.starts_with('0'),
+            },
+            _ => return None,
+        })
+    }
+}
+
+/// Was neben den Zügen während einer Verbindung passiert, zur Anzeige im Programm
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum DxpMeldung {
+    Chat(String),
+    /// Die Gegenseite `name` hat die Einladung nicht angenommen
+    Abgelehnt {
+        name: String,
+        annahme: Annahme,
+    },
+    /// Eine Partie gegen die Gegenseite mit diesem Namen beginnt
+    Partie(String),
+    /// Eine Nachricht, die nicht gelesen werden konnte und übergangen wurde
+    Unbekannt(String),
+}
+
+/// Eine Verbindung, über die DXP-Nachrichten verschickt werden
+pub struct DxpVerbindung {
+    stream: TcpStream,
+    leser: BufReader<TcpStream>,
+}
+
+impl DxpVerbindung {
+    pub fn new(stream: TcpStream) -> io::Result<Self> {
+        stream.set_nodelay(true)?;
+        Ok(Self {
+            leser: BufReader::new(stream.try_clone()?),
+            stream,
+        })
+    }
+
+    pub fn sende(&mut self, nachricht: &DxpNachricht) -> io::Result<()> {
+        let mut daten = nachricht.to_text().into_bytes();
+        daten.push(0);
+        self.stream.write_all(&daten)
+    }
+
+    /// Wartet auf die nächste Nachricht. Nachrichten, die nicht gelesen werden können, werden
+    /// an `melde` gegeben und übergangen. Gibt `None` zurück, wenn die Gegenseite die
+    /// Verbindung beendet hat.
+    pub fn empfange(
+        &mut self,
+        mut melde: impl FnMut(DxpMeldung),
+    ) -> io::Result<Option<DxpNachricht>> {
+        loop {
+            let mut daten = Vec::new();
+            if self.leser.read_until(0, &mut daten)? == 0 {
+                return Ok(None);
+            }
+            if daten.last() == Some(&0) {
+                daten.pop();
+            }
+            let text = String::from_utf8_lossy(&daten);
+            match DxpNachricht::parse(&text) {
+                Some(nachricht) => return Ok(Some(nachricht)),
+                None => melde(DxpMeldung::Unbekannt(text.into_owned())),
+            }
+        }
+    }
+}
+
+fn fehler(text: &str) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, text.to_string())
+}
+
+/// Unsere Engine als Teilnehmer an DXP-Partien
+pub struct DxpEngine {
+    pub name: String,
+    pub einstellungen: SuchEinstellungen,
+    pub buch: Option<Eroeffnungsbuch>,
+    pub datenbank: Option<Endspieldatenbank>,
+}
+
+impl DxpEngine {
+    pub fn new(name: &str, einstellungen: SuchEinstellungen) -> Self {
+        Self {
+            name: name.to_string(),
+            einstellungen,
+            buch: None,
+            datenbank: None,
+        }
+    }
+
+    /// Lädt zu einer Partie ein und spielt sie. Gibt `None` zurück, wenn die Gegenseite
+    /// ablehnt. Chat und Ablehnung gehen an `melde`.
+    pub fn spiele_als_initiator(
+        &self,
+        verbindung: &mut DxpVerbindung,
+        anfrage: &Spielanfrage,
+        weiter: bool,
+        mut melde: impl FnMut(DxpMeldung),
+    ) -> io::Result<Option<Ergebnis>> {
+        verbindung.sende(&DxpNachricht::Spielanfrage(anfrage.clone()))?;
+        loop {
+            match verbindung.empfange(&mut melde)? {
+                None => return Err(ErrorKind::UnexpectedEof.into()),
+                Some(DxpNachricht::Spielannahme { name, annahme }) => {
+                    if annahme != Annahme::Angenommen {
+                        melde(DxpMeldung::Abgelehnt { name, annahme });
+                        return Ok(None);
+                    }
+                    break;
+                }
+                Some(DxpNachricht::Chat(text)) => melde(DxpMeldung::Chat(text)),
+                Some(_) => return Err(fehler("GAMEACC erwartet")),
+            }
+        }
+        let eigene_farbe = !anfrage.follower_farbe;
+        let ergebnis = self.spiele(verbindung, anfrage, eigene_farbe, weiter, &mut melde)?;
+        Ok(Some(ergebnis))
+    }
+
+    /// Nimmt Einladungen an und spielt, bis die Gegenseite die Verbindung beendet oder keine
+    /// weitere Partie will. Gibt die Ergebnisse aller Partien zurück. Chat und der Beginn
+    /// jeder Partie gehen an `melde`.
+    pub fn spiele_als_follower(
+        &self,
+        verbindung: &mut DxpVerbindung,
+        mut melde: impl FnMut(DxpMeldung),
+    ) -> io::Result<Vec<Ergebnis>> {
+        let mut ergebnisse = Vec::new();
+        loop {
+            let anfrage = match verbindung.empfange(&mut melde)? {
+                None => return Ok(ergebnisse),
+                Some(DxpNachricht::Spielanfrage(anfrage)) => anfrage,
+                Some(DxpNachricht::Chat(text)) => {
+                    melde(DxpMeldung::Chat(text));
+                    continue;
+                }
+                // Reste einer beendeten Partie
+                Some(_) => continue,
+            };
+            let (start, am_zug) = anfrage.get_start();
+            let annahme = if start.pruefe_aufstellung(am_zug).is_empty() {
+                Annahme::Angenommen
+            } else {
+                Annahme::NichtMitDieserStellung
+            };
+            verbindung.sende(&DxpNachricht::Spielannahme {
+                name: self.name.clone(),
+                annahme,
+            })?;
+            if annahme != Annahme::Angenommen {
+                continue;
+            }
+            melde(DxpMeldung::Partie(anfrage.name.clone()));
+            let eigene_farbe = anfrage.follower_farbe;
+            let ergebnis = self.spiele(verbindung, &anfrage, eigene_farbe, true, &mut melde)?;
+            ergebnisse.push(ergebnis);
+        }
+    }
+
+    fn get_bedenkzeit(&self, uhr: &Uhr, eigene_farbe: Spieler) -> Duration {
+        let zeit = uhr.get_zeit_fuer_zug(eigene_farbe);
+        match self.einstellungen.max_zeit {
+            Some(max_zeit) => zeit.min(max_zeit),
+            None => zeit,
+        }
+    }
+
+    /// Spielt eine angenommene Partie als `eigene_farbe`. Die Bedenkzeit gilt vereinfacht für
+    /// die ganze Partie.
+    fn spiele(
+        &self,
+        verbindung: &mut DxpVerbindung,
+        anfrage: &Spielanfrage,
+        eigene_farbe: Spieler,
+        weiter: bool,
+        melde: &mut impl FnMut(DxpMeldung),
+    ) -> io::Result<Ergebnis> {
+        let (start, start_am_zug) = anfrage.get_start();
+        let mut partie = Partie::new(start, start_am_zug, Remisregeln::default());
+        // Die Partie vor jedem Halbzug, um Züge zurückzunehmen
+        let mut verlauf: Vec<Partie> = Vec::new();
+        let mut uhr = Uhr::new(Zeitkontrolle::Zuschlag {
+            grundzeit: anfrage.bedenkzeit,
+            zuschlag: Duration::ZERO,
+        });
+        let mut ende_gesendet = false;
+
+        loop {
+            let spielstand = partie.get_spielstand();
+            if spielstand != Spielstand::Laeuft && !ende_gesendet {
+                verbindung.sende(&DxpNachricht::Spielende {
+                    grund: Endgrund::from_spielstand(spielstand, eigene_farbe),
+                    weiter,
+                })?;
+                ende_gesendet = true;
+            }
+
+            if spielstand == Spielstand::Laeuft
+                && partie.get_am_zug() == eigene_farbe
+                && !ende_gesendet
+            {
+                let mut einstellungen = self.einstellungen;
+                einstellungen.max_zeit = Some(self.get_bedenkzeit(&uhr, eigene_farbe));
+                let start = Instant::now();
+                let ergebnis = partie.get_best_move_mit_einstellungen(
+                    &einstellungen,
+                    self.buch.as_ref(),
+                    self.datenbank.as_ref(),
+                    None,
+                );
+                let zeit = start.elapsed();
+                uhr.vergehe(eigene_farbe, zeit);
+                let zug = DxpZug::new(partie.get_brett(), &ergebnis.best_move, eigene_farbe, zeit)
+                    .ok_or_else(|| fehler("Die Suche hat keinen erlaubten Zug gefunden"))?;
+                verbindung.sende(&DxpNachricht::Zug(zug))?;
+                verlauf.push(partie.clone());
+                partie
+                    .ziehe(ergebnis.best_move)
+                    .ok_or_else(|| fehler("Die Suche hat keinen erlaubten Zug gefunden"))?;
+                continue;
+            }
+
+            match verbindung.empfange(&mut *melde)? {
+                None => return Err(ErrorKind::UnexpectedEof.into()),
+                Some(DxpNachricht::Zug(zug)) => {
+                    let gegner = !eigene_farbe;
+                    let nachher = (partie.get_am_zug() == gegner
+                        && spielstand == Spielstand::Laeuft)
+                        .then(|| zug.find(partie.get_brett(), gegner))
+                        .flatten();
+                    let Some(nachher) = nachher else {
+                        verbindung.sende(&DxpNachricht::Chat(format!(
+                            "Ungültiger Zug {}-{}",
+                            zug.von, zug.nach
+                        )))?;
+                        verbindung.sende(&DxpNachricht::Spielende {
+                            grund: Endgrund::Unbekannt,
+                            weiter,
+                        })?;
+                        ende_gesendet = true;
+                        continue;
+                    };
+                    uhr.vergehe(gegner, zug.zeit);
+                    verlauf.push(partie.clone());
+                    partie
+                        .ziehe(nachher)
+                        .ok_or_else(|| fehler("Der Zug der Gegenseite ist nicht erlaubt"))?;
+                }
+                Some(DxpNachricht::Spielende { grund, .. }) => {
+                    if !ende_gesendet {
+                        verbindung.sende(&DxpNachricht::Spielende {
+                            grund: Endgrund::from_spielstand(spielstand, eigene_farbe),
+                            weiter,
+                        })?;
+                    }
+                    // Das Ergebnis auf dem Brett gilt, sonst das der Gegenseite
+                    return Ok(match spielstand {
+                        Spielstand::Gewonnen(spieler) => Ergebnis::Sieg(spieler),
+                        Spielstand::Remis(_) => Ergebnis::Unentschieden,
+                        Spielstand::Laeuft => grund.to_ergebnis(!eigene_farbe),
+                    });
+                }
+                Some(DxpNachricht::Zuruecknahme { zug_nummer, am_zug }) => {
+                    // Die Züge werden wie in PDN ab 1 gezählt, jeder mit einem Halbzug je Seite
+                    let halbzug =
+                        (zug_nummer.max(1) as usize - 1) * 2 + usize::from(am_zug != start_am_zug);
+                    let angenommen = halbzug < verlauf.len() && !ende_gesendet;
+                    if angenommen {
+                        partie = verlauf[halbzug].clone();
+                        verlauf.truncate(halbzug);
+                    }
+                    verbindung.sende(&DxpNachricht::ZuruecknahmeAntwort { angenommen })?;
+                }
+                Some(DxpNachricht::Chat(text)) => melde(DxpMeldung::Chat(text)),
+                Some(_) => (),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{net::TcpListener, thread};
+
+    use super::*;
+
+    #[test]
+    fn test_dxp_nachricht() {
+        let anfrage = Spielanfrage {
+            name: "Dame".to_string(),
+            follower_farbe: Spieler::Weiss,
+            bedenkzeit: Duration::from_secs(300),
+            zuege: 75,
+            start: None,
+        };
+        let text = DxpNachricht::Spielanfrage(anfrage.clone()).to_text();
+        assert_eq!(text, format!("R01Dame{}W005075A", " ".repeat(28)));
+
+        let zug = DxpZug {
+            zeit: Duration::from_secs(12),
+            von: 22,
+            nach: 15,
+            geschlagen: vec![18],
+        };
+        assert_eq!(DxpNachricht::Zug(zug.clone()).to_text(), "M001222150118");
+
+        let mut brett = SpielBrett::default();
+        brett.set(
+            Position::from_feld_nummer(1).unwrap(),
+            Feld::Dame(Spieler::Weiss),
+        );
+        brett.set(
+            Position::from_feld_nummer(32).unwrap(),
+            Feld::Stein(Spieler::Schwarz),
+        );
+        for nachricht in [
+            DxpNachricht::Chat("Viel Glück".to_string()),
+            DxpNachricht::Spielanfrage(anfrage.clone()),
+            DxpNachricht::Spielanfrage(Spielanfrage {
+                start: Some((brett, Spieler::Schwarz)),
+                ..anfrage
+            }),
+            DxpNachricht::Spielannahme {
+                name: "Gegner".to_string(),
+                annahme: Annahme::NichtMitDieserZeit,
+            },
+            DxpNachricht::Zug(zug),
+            DxpNachricht::Spielende {
+                grund: Endgrund::IchGewinne,
+                weiter: false,
+            },
+            DxpNachricht::Zuruecknahme {
+                zug_nummer: 12,
+                am_zug: Spieler::Weiss,
+            },
+            DxpNachricht::ZuruecknahmeAntwort { angenommen: true },
+        ] {
+            assert_eq!(DxpNachricht::parse(&nachricht.to_text()), Some(nachricht));
+        }
+        assert_eq!(DxpNachricht::parse("M0012"), None);
+        assert_eq!(DxpNachricht::parse("X"), None);
+    }
+
+    #[test]
+    fn test_dxp_zug() {
+        let brett = SpielBrett::parse(concat!(
+            "_ s _ _ \n",
+            " _ w _ _\n",
+            "_ _ _ _ \n",
+            " _ _ w _\n",
+            "_ _ _ _ \n",
+            " _ _ _ _\n",
+            "_ _ _ _ \n",
+            " _ _ _ _",
+        ))
+        .unwrap();
+        let (zug, nachher) = &brett.get_possible_zuege(Spieler::Schwarz)[0];
+        let dxp = DxpZug::new(&brett, nachher, Spieler::Schwarz, Duration::ZERO).unwrap();
+        assert_eq!((dxp.von, dxp.nach), (2, 20));
+        assert_eq!(dxp.geschlagen.len(), zug.geschlagen.len());
+        assert_eq!(dxp.find(&brett, Spieler::Schwarz).as_ref(), Some(nachher));
+        assert_eq!(dxp.find(&brett, Spieler::Weiss), None);
+    }
+
+    #[test]
+    fn test_dxp_partie() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let adresse = listener.local_addr().unwrap();
+        let follower = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut verbindung = DxpVerbindung::new(stream).unwrap();
+            let mut meldungen = Vec::new();
+            let ergebnisse = DxpEngine::new("Follower", SuchEinstellungen::new(2))
+                .spiele_als_follower(&mut verbindung, |meldung| meldungen.push(meldung))
+                .unwrap();
+            (ergebnisse, meldungen)
+        });
+
+        let mut verbindung = DxpVerbindung::new(TcpStream::connect(adresse).unwrap()).unwrap();
+        let anfrage = Spielanfrage {
+            name: "Initiator".to_string(),
+            follower_farbe: Spieler::Weiss,
+            bedenkzeit: Duration::from_secs(60),
+            zuege: 50,
+            start: None,
+        };
+        let ergebnis = DxpEngine::new("Initiator", SuchEinstellungen::new(3))
+            .spiele_als_initiator(&mut verbindung, &anfrage, false, |_| {})
+            .unwrap()
+            .unwrap();
+        assert_ne!(ergebnis, Ergebnis::Offen);
+        drop(verbindung);
+        assert_eq!(
+            follower.join().unwrap(),
+            (
+                vec![ergebnis],
+                vec![DxpMeldung::Partie("Initiator".to_string())]
+            )
+        );
+    }
+}
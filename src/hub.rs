@@ -0,0 +1,558 @@
+use std::{
+    io::{self, BufRead},
+    thread,
+    time::Duration,
+};
+
+use crate::{
+    ai::{Engine, EngineOptionen, StopSignal},
+    brett::{Feld, Partie, Position, SpielBrett, Spieler, Zug},
+    uhr::{Uhr, Zeitkontrolle},
+};
+
+// Das Hub-Protokoll stammt wie DamExchange aus dem internationalen Dame. Stellungen und Züge
+// werden hier mit den 32 Feldern unseres Bretts in PDN-Nummerierung geschrieben, Schwarz (B
+// für black) steht wie in PDN oben.
+
+pub const NAME: &str = "Dame";
+pub const VARIANTE: &str = "normal";
+const FELDER: usize = SpielBrett::SIZE * SpielBrett::SIZE / 2;
+/// Ohne Zeitbegrenzung und ohne Tiefe wird so tief gesucht, bei `go analyse` höchstens so tief
+const STANDARD_DEPTH: u32 = 7;
+const ANALYSE_DEPTH: u32 = 9;
+/// Mit Zeitbegrenzung bestimmt die Zeit, wie tief gesucht wird, die Tiefe ist nur eine Obergrenze
+const ZEIT_DEPTH: u32 = 30;
+
+fn spieler_to_farbe(spieler: Spieler) -> char {
+    match spieler {
+        Spieler::Schwarz => 'B',
+        Spieler::Weiss => 'W',
+    }
+}
+
+fn farbe_to_spieler(farbe: char) -> Option<Spieler> {
+    match farbe {
+        'B' => Some(Spieler::Schwarz),
+        'W' => Some(Spieler::Weiss),
+        _ => None,
+    }
+}
+
+fn feld_to_zeichen(feld: Feld) -> char {
+    match feld {
+        Feld::Leer => 'e',
+        Feld::Stein(Spieler::Schwarz) => 'b',
+        Feld::Stein(Spieler::Weiss) => 'w',
+        Feld::Dame(Spieler::Schwarz) => 'B',
+        Feld::Dame(Spieler::Weiss) => 'W',
+    }
+}
+
+fn zeichen_to_feld(zeichen: char) -> Option<Feld> {
+    Some(match zeichen {
+        'e' => Feld::Leer,
+        'b' => Feld::Stein(Spieler::Schwarz),
+        'w' => Feld::Stein(Spieler::Weiss),
+        'B' => Feld::Dame(Spieler::Schwarz),
+        'W' => Feld::Dame(Spieler::Weiss),
+        _ => return None,
+    })
+}
+
+/// Eine Stellung als Farbe am Zug gefolgt von einem Zeichen für jedes der 32 Felder
+pub fn to_stellung(brett: &SpielBrett, am_zug: Spieler) -> String {
+    let mut result = String::with_capacity(FELDER + 1);
+    result.push(spieler_to_farbe(am_zug));
+    for nummer in 1..=FELDER {
+        let pos = Position::from_feld_nummer(nummer).unwrap();
+        result.push(feld_to_zeichen(brett.get(pos)));
+    }
+    result
+}
+
+pub fn parse_stellung(text: &str) -> Option<(SpielBrett, Spieler)> {
+    let mut zeichen = text.trim().chars();
+    let am_zug = farbe_to_spieler(zeichen.next()?)?;
+    let mut brett = SpielBrett::default();
+    for nummer in 1..=FELDER {
+        let pos = Position::from_feld_nummer(nummer).unwrap();
+        brett.set(pos, zeichen_to_feld(zeichen.next()?)?);
+    }
+    zeichen.next().is_none().then_some((brett, am_zug))
+}
+
+/// Ein Zug wie im Hub-Protokoll: `von-nach` oder beim Schlagen `vonxnach` gefolgt von allen
+/// geschlagenen Feldern, damit auch Züge mit demselben Start und Ziel eindeutig sind
+pub fn to_hub_notation(zug: &Zug) -> String {
+    let von = zug.von().to_feld_nummer();
+    let nach = zug.nach().to_feld_nummer();
+    if !zug.is_schlagen() {
+        return format!("{von}-{nach}");
+    }
+    let mut result = format!("{von}x{nach}");
+    for pos in &zug.geschlagen {
+        result.push_str(&format!("x{}", pos.to_feld_nummer()));
+    }
+    result
+}
+
+/// Findet den Zug zu `notation`, die geschlagenen Felder dürfen in beliebiger Reihenfolge stehen
+pub fn find_hub_zug(brett: &SpielBrett, spieler: Spieler, notation: &str) -> Option<SpielBrett> {
+    let mut felder: Vec<usize> = notation
+        .trim()
+        .split(['-', 'x'])
+        .map(|nummer| nummer.parse().ok())
+        .collect::<Option<_>>()?;
+    if felder.len() < 2 {
+        return None;
+    }
+    felder[2..].sort_unstable();
+    brett
+        .get_possible_zuege(spieler)
+        .into_iter()
+        .find(|(zug, _)| {
+            let mut geschlagen: Vec<usize> = zug
+                .geschlagen
+                .iter()
+                .map(|pos| pos.to_feld_nummer())
+                .collect();
+            geschlagen.sort_unstable();
+            zug.von().to_feld_nummer() == felder[0]
+                && zug.nach().to_feld_nummer() == felder[1]
+                && (felder.len() == 2 || felder[2..] == geschlagen)
+        })
+        .map(|(_, nachher)| nachher)
+}
+
+/// Setzt Werte mit Leerzeichen in Anführungszeichen
+fn format_wert(wert: &str) -> String {
+    if wert.is_empty() || wert.contains(' ') {
+        format!("\"{wert}\"")
+    } else {
+        wert.to_string()
+    }
+}
+
+/// Eine Zeile des Protokolls aus einem Befehl und Paaren aus Name und Wert wie
+/// `pos pos=... moves="11-15 22-18"`. Namen ohne Wert wie bei `go think` haben einen leeren Wert.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct HubZeile {
+    pub befehl: String,
+    pub werte: Vec<(String, String)>,
+}
+
+impl HubZeile {
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        let (befehl, mut rest) = text.split_once(' ').unwrap_or((text, ""));
+        if befehl.is_empty() {
+            return None;
+        }
+        let mut werte = Vec::new();
+        loop {
+            rest = rest.trim_start();
+            if rest.is_empty() {
+                break;
+            }
+            let ende = rest.find([' ', '=']).unwrap_or(rest.len());
+            let name = &rest[..ende];
+            rest = &rest[ende..];
+            let mut wert = "";
+            if let Some(nach_gleich) = rest.strip_prefix('=') {
+                if let Some(in_anfuehrung) = nach_gleich.strip_prefix('"') {
+                    (wert, rest) = in_anfuehrung.split_once('"')?;
+                } else {
+                    let ende = nach_gleich.find(' ').unwrap_or(nach_gleich.len());
+                    (wert, rest) = nach_gleich.split_at(ende);
+                }
+            }
+            werte.push((name.to_string(), wert.to_string()));
+        }
+        Some(Self {
+            befehl: befehl.to_string(),
+            werte,
+        })
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.werte
+            .iter()
+            .find(|(eigener_name, _)| eigener_name == name)
+            .map(|(_, wert)| wert.as_str())
+    }
+
+    pub fn has(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// `Some(None)`, wenn der Wert fehlt, und `None`, wenn er keine Zahl von Sekunden ist
+    fn get_sekunden(&self, name: &str) -> Option<Option<Duration>> {
+        match self.get(name) {
+            None => Some(None),
+            Some(wert) => Duration::try_from_secs_f64(wert.parse().ok()?)
+                .ok()
+                .map(Some),
+        }
+    }
+}
+
+/// Die Bedenkzeit aus dem Befehl `level`
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Level {
+    pub depth: Option<u32>,
+    /// Eine feste Zeit für jeden Zug
+    pub zeit_pro_zug: Option<Duration>,
+    /// Die Restzeit für die Partie oder bis zur nächsten Zeitkontrolle
+    pub restzeit: Option<Duration>,
+    pub zuschlag: Duration,
+    /// Wie viele Züge bis zur nächsten Zeitkontrolle bleiben
+    pub zuege: Option<u32>,
+}
+
+impl Level {
+    fn parse(zeile: &HubZeile) -> Option<Self> {
+        if zeile.has("infinite") {
+            return Some(Self::default());
+        }
+        Some(Self {
+            depth: zeile.get("depth").map(str::parse).transpose().ok()?,
+            zeit_pro_zug: zeile.get_sekunden("move-time")?,
+            restzeit: zeile.get_sekunden("time")?,
+            zuschlag: zeile.get_sekunden("inc")?.unwrap_or_default(),
+            zuege: zeile.get("moves").map(str::parse).transpose().ok()?,
+        })
+    }
+
+    /// Wie lange `am_zug` über den nächsten Zug nachdenken sollte, `None` ohne Zeitbegrenzung
+    pub fn get_bedenkzeit(&self, am_zug: Spieler) -> Option<Duration> {
+        if let Some(zeit) = self.zeit_pro_zug {
+            return Some(zeit);
+        }
+        let restzeit = self.restzeit?;
+        Some(match self.zuege {
+            Some(zuege) => (restzeit / zuege.max(1) + self.zuschlag * 3 / 4).min(restzeit / 2),
+            None => Uhr::new(Zeitkontrolle::Zuschlag {
+                grundzeit: restzeit,
+                zuschlag: self.zuschlag,
+            })
+            .get_zeit_fuer_zug(am_zug),
+        })
+    }
+}
+
+/// Der Zustand der Engine zwischen den Befehlen. Gesucht wird auf einer Kopie, damit die Suche
+/// in einem eigenen Thread laufen kann.
+#[derive(Clone, Debug)]
+pub struct Hub {
+    engine: Engine,
+    level: Level,
+    /// Ob das Eröffnungsbuch benutzt wird, lässt sich mit dem Parameter `book` einstellen
+    mit_buch: bool,
+}
+
+impl Default for Hub {
+    fn default() -> Self {
+        Self::new(Engine::default())
+    }
+}
+
+impl Hub {
+    pub fn new(engine: Engine) -> Self {
+        Self {
+            engine,
+            level: Level::default(),
+            mit_buch: true,
+        }
+    }
+
+    pub fn get_partie(&self) -> &Partie {
+        self.engine.get_partie()
+    }
+
+    pub fn get_level(&self) -> Level {
+        self.level
+    }
+
+    pub fn get_stop_signal(&self) -> StopSignal {
+        self.engine.get_stop_signal()
+    }
+
+    /// Beantwortet alle Befehle außer `go`, `stop` und `quit`, die den Such-Thread betreffen
+    pub fn verarbeite(&mut self, zeile: &HubZeile) -> Vec<String> {
+        match zeile.befehl.as_str() {
+            "hub" => vec![
+                format!(
+                    "id name={NAME} version={}",
+                    format_wert(env!("CARGO_PKG_VERSION"))
+                ),
+                format!("param name=variant value={VARIANTE} type=enum values={VARIANTE}"),
+                format!("param name=book value={} type=bool", self.mit_buch),
+                format!(
+                    "param name=threads value={} type=int min=1 max=64",
+                    self.engine.get_optionen().threads
+                ),
+                format!(
+                    "param name=tt-size value={} type=int min=1 max=65536",
+                    self.engine.get_optionen().hash_mb
+                ),
+                "wait".to_string(),
+            ],
+            "init" => vec!["ready".to_string()],
+            "ping" => vec!["pong".to_string()],
+            "new-game" => {
+                self.engine
+                    .set_position(SpielBrett::startaufstellung(), Spieler::Schwarz);
+                Vec::new()
+            }
+            "set-param" => match self.setze_parameter(zeile) {
+                Some(()) => Vec::new(),
+                None => vec![fehler(&format!(
+                    "Ungültiger Parameter {}",
+                    zeile.get("name").unwrap_or("")
+                ))],
+            },
+            "level" => match Level::parse(zeile) {
+                Some(level) => {
+                    self.level = level;
+                    Vec::new()
+                }
+                None => vec![fehler("Ungültige Zeitangabe")],
+            },
+            "pos" => match self.setze_stellung(zeile) {
+                Some(partie) => {
+                    self.engine.set_partie(partie);
+                    Vec::new()
+                }
+                None => vec![fehler("Ungültige Stellung oder ungültiger Zug")],
+            },
+            befehl => vec![fehler(&format!("Unbekannter Befehl {befehl}"))],
+        }
+    }
+
+    fn setze_parameter(&mut self, zeile: &HubZeile) -> Option<()> {
+        let wert = zeile.get("value")?;
+        let mut optionen = self.engine.get_optionen();
+        match zeile.get("name")? {
+            "variant" if wert == VARIANTE => {}
+            "book" => self.mit_buch = wert.parse().ok()?,
+            "threads" => optionen.threads = wert.parse().ok().filter(|&threads| threads > 0)?,
+            "tt-size" => optionen.hash_mb = wert.parse().ok()?,
+            _ => return None,
+        }
+        self.engine.set_optionen(optionen);
+        Some(())
+    }
+
+    fn setze_stellung(&self, zeile: &HubZeile) -> Option<Partie> {
+        let (brett, am_zug) = match zeile.get("pos") {
+            Some(stellung) => parse_stellung(stellung)?,
+            None => (SpielBrett::startaufstellung(), Spieler::Schwarz),
+        };
+        let mut partie = Partie::new(brett, am_zug, self.get_partie().get_regeln());
+        for notation in zeile.get("moves").unwrap_or("").split_whitespace() {
+            let nachher = find_hub_zug(partie.get_brett(), partie.get_am_zug(), notation)?;
+            partie.ziehe(nachher)?;
+        }
+        Some(partie)
+    }
+
+    /// Sucht mit der `Engine` und gibt nach jeder Tiefe eine `info`-Zeile und am Ende `done`
+    /// aus. Eine Analyse sucht ohne Eröffnungsbuch und wartet mit `done`, bis das
+    /// `StopSignal` gesetzt wird.
+    pub fn suche(&self, analyse: bool, mut ausgabe: impl FnMut(String)) {
+        let mut engine = self.engine.clone();
+        let bedenkzeit = match analyse {
+            true => None,
+            false => self.level.get_bedenkzeit(self.get_partie().get_am_zug()),
+        };
+        engine.set_optionen(EngineOptionen {
+            max_depth: match (self.level.depth, analyse, bedenkzeit) {
+                (Some(depth), _, _) => depth,
+                (None, true, _) => ANALYSE_DEPTH,
+                (None, false, Some(_)) => ZEIT_DEPTH,
+                (None, false, None) => STANDARD_DEPTH,
+            },
+            max_zeit: bedenkzeit,
+            ..engine.get_optionen()
+        });
+        if analyse || !self.mit_buch {
+            engine.set_eroeffnungsbuch(None);
+        }
+
+        let ergebnis = engine.suche(|ergebnis| {
+            let sekunden = ergebnis.zeit.as_secs_f64();
+            let nps = match sekunden {
+                sekunden if sekunden > 0.0 => ergebnis.nodes as f64 / sekunden,
+                _ => 0.0,
+            };
+            let hauptvariante: Vec<String> =
+                ergebnis.hauptvariante.iter().map(to_hub_notation).collect();
+            ausgabe(format!(
+                "info depth={} score={:.2} nodes={} time={sekunden:.3} nps={nps:.0} pv={}",
+                ergebnis.depth,
+                ergebnis.bewertung as f64 / 100.0,
+                ergebnis.nodes,
+                format_wert(&hauptvariante.join(" ")),
+            ));
+        });
+        let stop = engine.get_stop_signal();
+        while analyse && !stop.is_gestoppt() {
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        let zuege = ergebnis.map_or(Vec::new(), |ergebnis| ergebnis.hauptvariante);
+        ausgabe(match zuege.as_slice() {
+            [] => "done".to_string(),
+            [zug] => format!("done move={}", to_hub_notation(zug)),
+            [zug, antwort, ..] => format!(
+                "done move={} ponder={}",
+                to_hub_notation(zug),
+                to_hub_notation(antwort)
+            ),
+        });
+    }
+}
+
+fn fehler(nachricht: &str) -> String {
+    format!("error message={}", format_wert(nachricht))
+}
+
+fn beende_suche(suche: &mut Option<thread::JoinHandle<()>>, stop: &StopSignal) {
+    if let Some(suche) = suche.take() {
+        stop.stop();
+        let _ = suche.join();
+    }
+}
+
+/// Liest Befehle von der Standardeingabe und antwortet auf der Standardausgabe, bis `quit`
+/// kommt oder die Eingabe endet. Gesucht wird in einem eigenen Thread, damit `stop` und
+/// `ping` auch während der Suche beantwortet werden.
+pub fn run(mut hub: Hub) -> io::Result<()> {
+    let stop = hub.get_stop_signal();
+    let mut suche = None;
+    for zeile in io::stdin().lock().lines() {
+        let Some(zeile) = HubZeile::parse(&zeile?) else {
+            continue;
+        };
+        match zeile.befehl.as_str() {
+            "go" => {
+                beende_suche(&mut suche, &stop);
+                // Auf den Zug des Gegners wird nicht vorausgedacht, `ponder` ist eine Analyse
+                let analyse = ["analyse", "analyze", "ponder"]
+                    .iter()
+                    .any(|name| zeile.has(name));
+                // Vor dem Start zurücksetzen, damit ein gleich folgendes `stop` nicht verloren
+                // geht und die Analyse immer mit `done` endet
+                stop.zuruecksetzen();
+                let hub = hub.clone();
+                suche = Some(thread::spawn(move || {
+                    hub.suche(analyse, |zeile| println!("{zeile}"))
+                }));
+            }
+            "stop" => stop.stop(),
+            "ponder-hit" => {}
+            "quit" => break,
+            _ => {
+                for antwort in hub.verarbeite(&zeile) {
+                    println!("{antwort}");
+                }
+            }
+        }
+    }
+    beende_suche(&mut suche, &stop);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hub_zeile() {
+        let zeile = HubZeile::parse("pos pos=Weee moves=\"11-15 22-18\" infinite").unwrap();
+        assert_eq!(zeile.befehl, "pos");
+        assert_eq!(zeile.get("pos"), Some("Weee"));
+        assert_eq!(zeile.get("moves"), Some("11-15 22-18"));
+        assert!(zeile.has("infinite"));
+        assert!(!zeile.has("depth"));
+        assert_eq!(HubZeile::parse("   "), None);
+        assert_eq!(HubZeile::parse("pos moves=\"11-15"), None);
+
+        let level = Level::parse(&HubZeile::parse("level time=60 inc=0.5 moves=20").unwrap());
+        let level = level.unwrap();
+        assert_eq!(level.restzeit, Some(Duration::from_secs(60)));
+        assert_eq!(level.zuschlag, Duration::from_millis(500));
+        assert!(level.get_bedenkzeit(Spieler::Weiss).unwrap() < Duration::from_secs(4));
+        assert_eq!(
+            Level::parse(&HubZeile::parse("level depth=x").unwrap()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_stellung_und_zuege() {
+        let brett = SpielBrett::startaufstellung();
+        let stellung = to_stellung(&brett, Spieler::Schwarz);
+        assert_eq!(
+            stellung,
+            format!("B{}{}{}", "b".repeat(12), "e".repeat(8), "w".repeat(12))
+        );
+        assert_eq!(
+            parse_stellung(&stellung),
+            Some((brett.clone(), Spieler::Schwarz))
+        );
+        assert_eq!(parse_stellung(&stellung[..20]), None);
+
+        let nachher = find_hub_zug(&brett, Spieler::Schwarz, "11-15").unwrap();
+        assert_eq!(nachher, brett.find_zug(Spieler::Schwarz, "11-15").unwrap());
+        let nachher = find_hub_zug(&nachher, Spieler::Weiss, "24-20").unwrap();
+        let zuege = nachher.get_possible_zuege(Spieler::Schwarz);
+        assert_eq!(zuege.len(), 1);
+        assert_eq!(to_hub_notation(&zuege[0].0), "15x24x20");
+        assert!(find_hub_zug(&nachher, Spieler::Schwarz, "15x24x20").is_some());
+        assert!(find_hub_zug(&nachher, Spieler::Schwarz, "15x24x19").is_none());
+        assert!(find_hub_zug(&nachher, Spieler::Schwarz, "9-13").is_none());
+    }
+
+    #[test]
+    fn test_hub() {
+        let mut hub = Hub::default();
+        let mut befehl = |text: &str| hub.verarbeite(&HubZeile::parse(text).unwrap());
+        let antworten = befehl("hub");
+        assert!(antworten[0].starts_with("id name=Dame"));
+        assert_eq!(antworten.last().unwrap(), "wait");
+        assert_eq!(befehl("init"), vec!["ready"]);
+        assert!(befehl("set-param name=variant value=normal").is_empty());
+        assert!(befehl("set-param name=variant value=frisian")[0].starts_with("error"));
+        assert!(befehl("set-param name=threads value=2").is_empty());
+        assert!(befehl("set-param name=threads value=0")[0].starts_with("error"));
+        assert!(befehl("pos moves=\"11-15 24-20\"").is_empty());
+        assert!(befehl("pos moves=\"11-15 11-15\"")[0].starts_with("error"));
+        assert!(befehl("level depth=3").is_empty());
+        assert!(befehl("frage")[0].starts_with("error"));
+        assert_eq!(hub.get_partie().get_am_zug(), Spieler::Schwarz);
+
+        let mut ausgabe = Vec::new();
+        hub.suche(false, |zeile| ausgabe.push(zeile));
+        assert_eq!(ausgabe.len(), 4);
+        assert!(ausgabe[2].starts_with("info depth=3 score="));
+        assert!(ausgabe[2].contains("pv=\"15x24x20 "));
+        assert!(ausgabe[3].starts_with("done move=15x24x20 ponder="));
+
+        // Ein `stop` vor dem Start beendet auch eine Analyse nach der ersten Tiefe
+        hub.get_stop_signal().stop();
+        let mut ausgabe = Vec::new();
+        hub.suche(true, |zeile| ausgabe.push(zeile));
+        assert_eq!(ausgabe.len(), 2);
+        assert!(ausgabe[0].starts_with("info depth=1 "));
+        assert!(ausgabe[1].starts_with("done move=15x24x20"));
+
+        // Ohne Züge gibt es keinen Zug zu melden
+        let mut ende = Hub::default();
+        let stellung = format!("W{}", "e".repeat(31) + "b");
+        ende.verarbeite(&HubZeile::parse(&format!("pos pos={stellung}")).unwrap());
+        let mut ausgabe = Vec::new();
+        ende.suche(false, |zeile| ausgabe.push(zeile));
+        assert_eq!(ausgabe, vec!["done"]);
+    }
+}
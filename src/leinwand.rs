@@ -0,0 +1,246 @@
+mod schrift;
+
+/// Eine Farbe als `0RGB`, wie sie minifb erwartet
+pub type Color = u32;
+
+pub const fn rgb(r: u8, g: u8, b: u8) -> Color {
+    let (r, g, b) = (r as u32, g as u32, b as u32);
+    (r << 16) | (g << 8) | b
+}
+
+fn get_kanaele(color: Color) -> [f64; 3] {
+    [
+        ((color >> 16) & 0xFF) as f64,
+        ((color >> 8) & 0xFF) as f64,
+        (color & 0xFF) as f64,
+    ]
+}
+
+fn from_kanaele([r, g, b]: [f64; 3]) -> Color {
+    let kanal = |wert: f64| wert.round().clamp(0.0, 255.0) as u8;
+    rgb(kanal(r), kanal(g), kanal(b))
+}
+
+/// Mischt `vordergrund` mit dem Anteil `deckung` zwischen 0 und 1 über `hintergrund`
+pub fn mische(hintergrund: Color, vordergrund: Color, deckung: f64) -> Color {
+    let deckung = deckung.clamp(0.0, 1.0);
+    let hintergrund = get_kanaele(hintergrund);
+    let vordergrund = get_kanaele(vordergrund);
+    from_kanaele(
+        [0, 1, 2]
+            .map(|index| hintergrund[index] + (vordergrund[index] - hintergrund[index]) * deckung),
+    )
+}
+
+/// Macht eine Farbe mit einem Faktor über 1 heller und mit einem Faktor unter 1 dunkler
+pub fn helligkeit(color: Color, faktor: f64) -> Color {
+    if faktor > 1.0 {
+        mische(color, rgb(255, 255, 255), faktor - 1.0)
+    } else {
+        mische(rgb(0, 0, 0), color, faktor)
+    }
+}
+
+/// Ein Bildspeicher, in den ohne GPU gezeichnet wird. Alles außerhalb wird abgeschnitten.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Leinwand {
+    breite: usize,
+    hoehe: usize,
+    buffer: Vec<Color>,
+    deckung: f64,
+}
+
+impl Leinwand {
+    pub fn new(breite: usize, hoehe: usize) -> Self {
+        Self {
+            breite,
+            hoehe,
+            buffer: vec![0; breite * hoehe],
+            deckung: 1.0,
+        }
+    }
+
+    pub fn get_breite(&self) -> usize {
+        self.breite
+    }
+
+    pub fn get_hoehe(&self) -> usize {
+        self.hoehe
+    }
+
+    pub fn get_buffer(&self) -> &[Color] {
+        &self.buffer
+    }
+
+    pub fn get_pixel(&self, x: usize, y: usize) -> Option<Color> {
+        if x >= self.breite || y >= self.hoehe {
+            return None;
+        }
+        Some(self.buffer[y * self.breite + x])
+    }
+
+    pub fn set_pixel(&mut self, x: usize, y: usize, color: Color) {
+        if x < self.breite && y < self.hoehe {
+            self.buffer[y * self.breite + x] = color;
+        }
+    }
+
+    /// Macht alles, was danach gemischt gezeichnet wird, durchsichtiger. `draw_rect` und
+    /// `set_pixel` sind davon nicht betroffen.
+    pub fn set_deckung(&mut self, deckung: f64) {
+        self.deckung = deckung.clamp(0.0, 1.0);
+    }
+
+    /// Mischt `color` mit dem Anteil `deckung` über den vorhandenen Pixel
+    pub fn mische_pixel(&mut self, x: usize, y: usize, color: Color, deckung: f64) {
+        if let Some(hintergrund) = self.get_pixel(x, y) {
+            self.set_pixel(x, y, mische(hintergrund, color, deckung * self.deckung));
+        }
+    }
+
+    pub fn fill(&mut self, color: Color) {
+        self.buffer.fill(color);
+    }
+
+    pub fn draw_rect(
+        &mut self,
+        x_start: usize,
+        y_start: usize,
+        width: usize,
+        height: usize,
+        color: Color,
+    ) {
+        for y in y_start..(y_start + height).min(self.hoehe) {
+            for x in x_start..(x_start + width).min(self.breite) {
+                self.buffer[y * self.breite + x] = color;
+            }
+        }
+    }
+
+    /// Ruft `farbe` für jeden Pixel im Rechteck um `(x, y)` mit dem Abstand zum Mittelpunkt
+    /// auf und mischt die zurückgegebene Farbe mit ihrer Deckung über den Pixel.
+    fn draw_rund(
+        &mut self,
+        x: f64,
+        y: f64,
+        radius: f64,
+        mut farbe: impl FnMut(f64, f64) -> Option<(Color, f64)>,
+    ) {
+        let x_start = (x - radius - 1.0).max(0.0) as usize;
+        let y_start = (y - radius - 1.0).max(0.0) as usize;
+        let x_ende = ((x + radius + 1.0).max(0.0) as usize).min(self.breite);
+        let y_ende = ((y + radius + 1.0).max(0.0) as usize).min(self.hoehe);
+        for pixel_y in y_start..y_ende {
+            for pixel_x in x_start..x_ende {
+                let dx = pixel_x as f64 + 0.5 - x;
+                let dy = pixel_y as f64 + 0.5 - y;
+                if let Some((color, deckung)) = farbe(dx, dy) {
+                    self.mische_pixel(pixel_x, pixel_y, color, deckung);
+                }
+            }
+        }
+    }
+
+    /// Ein Kreis mit geglättetem Rand
+    pub fn draw_kreis(&mut self, x: f64, y: f64, radius: f64, color: Color, deckung: f64) {
+        self.draw_rund(x, y, radius, |dx, dy| {
+            let abstand = (dx * dx + dy * dy).sqrt();
+            Some((color, (radius - abstand + 0.5).clamp(0.0, 1.0) * deckung))
+        });
+    }
+
+    /// Ein Kreis, der wie von oben links beleuchtet aussieht
+    pub fn draw_kugel(&mut self, x: f64, y: f64, radius: f64, color: Color) {
+        self.draw_rund(x, y, radius, |dx, dy| {
+            let abstand = (dx * dx + dy * dy).sqrt();
+            let licht = -(dx + dy) / (radius * std::f64::consts::SQRT_2);
+            let rand = (abstand / radius).powi(4);
+            let faktor = 1.0 + 0.25 * licht - 0.25 * rand;
+            Some((
+                helligkeit(color, faktor),
+                (radius - abstand + 0.5).clamp(0.0, 1.0),
+            ))
+        });
+    }
+
+    /// Ein Ring zwischen den Radien `innen` und `aussen` mit geglätteten Rändern
+    pub fn draw_ring(&mut self, x: f64, y: f64, innen: f64, aussen: f64, color: Color) {
+        self.draw_rund(x, y, aussen, |dx, dy| {
+            let abstand = (dx * dx + dy * dy).sqrt();
+            let deckung = (aussen - abstand + 0.5).min(abstand - innen + 0.5);
+            Some((color, deckung.clamp(0.0, 1.0)))
+        });
+    }
+
+    /// Ein gefülltes konvexes oder konkaves Polygon. Die Kanten werden durch 4x4 Abtastpunkte
+    /// pro Pixel geglättet.
+    pub fn draw_polygon(&mut self, punkte: &[(f64, f64)], color: Color) {
+        if punkte.len() < 3 {
+            return;
+        }
+        let x_min = punkte.iter().map(|p| p.0).fold(f64::MAX, f64::min).max(0.0) as usize;
+        let y_min = punkte.iter().map(|p| p.1).fold(f64::MAX, f64::min).max(0.0) as usize;
+        let x_max = (punkte.iter().map(|p| p.0).fold(f64::MIN, f64::max).max(0.0) as usize + 1)
+            .min(self.breite);
+        let y_max = (punkte.iter().map(|p| p.1).fold(f64::MIN, f64::max).max(0.0) as usize + 1)
+            .min(self.hoehe);
+
+        let innerhalb = |x: f64, y: f64| {
+            let mut innerhalb = false;
+            let mut vorher = punkte[punkte.len() - 1];
+            for &punkt in punkte {
+                if (punkt.1 > y) != (vorher.1 > y)
+                    && x < (vorher.0 - punkt.0) * (y - punkt.1) / (vorher.1 - punkt.1) + punkt.0
+                {
+                    innerhalb = !innerhalb;
+                }
+                vorher = punkt;
+            }
+            innerhalb
+        };
+
+        for y in y_min..y_max {
+            for x in x_min..x_max {
+                let mut treffer = 0;
+                for unter_y in 0..4 {
+                    for unter_x in 0..4 {
+                        let abtast_x = x as f64 + (unter_x as f64 + 0.5) / 4.0;
+                        let abtast_y = y as f64 + (unter_y as f64 + 0.5) / 4.0;
+                        treffer += usize::from(innerhalb(abtast_x, abtast_y));
+                    }
+                }
+                if treffer > 0 {
+                    self.mische_pixel(x, y, color, treffer as f64 / 16.0);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_leinwand() {
+        let mut leinwand = Leinwand::new(20, 10);
+        leinwand.draw_rect(15, 5, 10, 10, rgb(255, 0, 0));
+        assert_eq!(leinwand.get_pixel(19, 9), Some(rgb(255, 0, 0)));
+        assert_eq!(leinwand.get_pixel(14, 9), Some(0));
+        assert_eq!(leinwand.get_pixel(20, 0), None);
+
+        leinwand.fill(0);
+        leinwand.draw_kreis(5.0, 5.0, 3.0, rgb(0, 0, 200), 1.0);
+        assert_eq!(leinwand.get_pixel(5, 5), Some(rgb(0, 0, 200)));
+        assert_eq!(leinwand.get_pixel(0, 0), Some(0));
+        // Der Rand ist nur teilweise gedeckt
+        let rand = leinwand.get_pixel(7, 6).unwrap();
+        assert!(rand > 0 && rand < 200);
+
+        assert_eq!(mische(rgb(0, 0, 0), rgb(200, 100, 0), 0.5), rgb(100, 50, 0));
+
+        leinwand.set_deckung(0.5);
+        leinwand.mische_pixel(0, 0, rgb(200, 100, 0), 1.0);
+        assert_eq!(leinwand.get_pixel(0, 0), Some(rgb(100, 50, 0)));
+    }
+}
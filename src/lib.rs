@@ -1,2 +1,11 @@
 pub mod ai;
 pub mod brett;
+pub mod dxp;
+pub mod hub;
+pub mod leinwand;
+pub mod netz;
+pub mod pdn;
+pub mod ton;
+pub mod uhr;
+#[cfg(target_arch = "wasm32")]
+pub mod wasm;
@@ -1,56 +1,350 @@
-use dame::brett::{Feld, Position, SpielBrett, Spieler};
-use minifb::{Key, MouseMode, Window, WindowOptions};
+use dame::{
+    ai::{
+        Endspieldatenbank, Engine, EngineOptionen, Eroeffnungsbuch, Gewichte, SuchEinstellungen,
+        Wert, Zufall, ZugAnalyse,
+    },
+    brett::{
+        Feld, Partie, Position, RemisGrund, Remisregeln, SpielBrett, Spieler, Spielstand, Zug,
+    },
+    hub::{self, Hub},
+    leinwand::{helligkeit, rgb, Color, Leinwand},
+    netz::{Ereignis, Netzpartie, Spielangebot, STANDARD_PORT},
+    ton::{Geraeusch, Lautsprecher},
+    uhr::{format_zeit, Uhr, Zeitkontrolle},
+};
+use minifb::{Key, MouseButton, MouseMode, ScaleMode, Window, WindowOptions};
+use std::{
+    env, io,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
+const VERWENDUNG: &str = "Verwendung: dame [--host [port] | --verbinde adresse[:port]] \
+[--zeit 300+3 | --zeit 10/zug] [--weiss]
+       dame --hub
+Mit --host wartet das Spiel auf einen Gegner im Netzwerk, der sich mit --verbinde anmeldet.
+Bedenkzeit und Farbe legt der Gastgeber fest, ohne --weiss spielt er Schwarz.
+Mit --hub läuft nur die Engine ohne Fenster und wird über das Hub-Protokoll gesteuert.";
+
+/// Die Größe eines Feldes beim Start, danach richtet sie sich nach dem Fenster
 const FELD_SIZE: usize = 100;
+const EROEFFNUNGSBUCH_DATEI: &str = "eroeffnungsbuch.txt";
+const ENDSPIELDATENBANK_DATEI: &str = "endspiel.edb";
+const GEWICHTE_DATEI: &str = "gewichte.txt";
 const SIZE: usize = FELD_SIZE * SpielBrett::SIZE;
+const PANEL_BREITE: usize = 320;
+/// Der Abstand des Inhalts der Seitenleiste zu ihrem Rand
+const PANEL_RAND: usize = 16;
+const ANALYSE_DEPTH: u32 = 5;
+const SUCH_DEPTH: u32 = 7;
+const SELBSTSPIEL_DEPTH: u32 = 5;
+/// Mit Uhr bestimmt die Zeit, wie tief gesucht wird, die Tiefe ist nur eine Obergrenze
+const UHR_DEPTH: u32 = 30;
+const ZEITKONTROLLEN: [Option<Zeitkontrolle>; 4] = [
+    None,
+    Some(Zeitkontrolle::Zuschlag {
+        grundzeit: Duration::from_secs(5 * 60),
+        zuschlag: Duration::from_secs(3),
+    }),
+    Some(Zeitkontrolle::Zuschlag {
+        grundzeit: Duration::from_secs(15 * 60),
+        zuschlag: Duration::from_secs(10),
+    }),
+    Some(Zeitkontrolle::ProZug(Duration::from_secs(10))),
+];
 
-type Color = u32;
+const BLACK: Color = rgb(0, 0, 0);
+const HELLES_FELD: Color = rgb(240, 217, 181);
+const DUNKLES_FELD: Color = rgb(181, 136, 99);
+const DUNKLE_FIGUR: Color = rgb(60, 52, 48);
+const HELLE_FIGUR: Color = rgb(238, 228, 205);
+const KRONE: Color = rgb(232, 182, 40);
+const YELLOW: Color = rgb(250, 220, 40);
+const GREEN: Color = rgb(60, 200, 90);
+const ORANGE: Color = rgb(255, 140, 0);
+const LETZTER_ZUG: Color = rgb(206, 172, 82);
+const PANEL: Color = rgb(44, 40, 38);
+const KNOPF: Color = rgb(74, 68, 64);
+const KNOPF_AKTIV: Color = rgb(46, 120, 64);
+const TEXT: Color = rgb(235, 228, 215);
+const TEXT_GRAU: Color = rgb(150, 142, 130);
 
-const fn rgb(r: u8, g: u8, b: u8) -> u32 {
-    let (r, g, b) = (r as u32, g as u32, b as u32);
-    (r << 16) | (g << 8) | b
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum AnimationsTempo {
+    Normal,
+    Schnell,
+    Aus,
 }
 
-const BLACK: Color = rgb(0, 0, 0);
-const WHITE: Color = rgb(255, 255, 255);
-const LIGHT_BLUE: Color = rgb(24, 197, 245);
-const DARK_BLUE: Color = rgb(16, 46, 235);
-const LIGHT_RED: Color = rgb(247, 35, 77);
-const DARK_RED: Color = rgb(130, 3, 28);
+impl AnimationsTempo {
+    /// Wie lange eine Figur braucht, um von einem Feld des Zugs zum nächsten zu gleiten
+    fn get_dauer_pro_schritt(self) -> Option<Duration> {
+        match self {
+            Self::Normal => Some(Duration::from_millis(300)),
+            Self::Schnell => Some(Duration::from_millis(100)),
+            Self::Aus => None,
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            Self::Normal => Self::Schnell,
+            Self::Schnell => Self::Aus,
+            Self::Aus => Self::Normal,
+        }
+    }
+}
+
+/// Eine Figur, die entlang des Pfads eines Zugs gleitet, während geschlagene Figuren
+/// ausgeblendet werden
+struct Animation {
+    vorher: SpielBrett,
+    zug: Zug,
+    dauer_pro_schritt: Duration,
+    verstrichen: Duration,
+}
+
+impl Animation {
+    fn get_schritte(&self) -> usize {
+        self.zug.pfad.len() - 1
+    }
+
+    fn is_fertig(&self) -> bool {
+        self.verstrichen >= self.dauer_pro_schritt * self.get_schritte() as u32
+    }
+
+    /// Der aktuelle Schritt und wie weit er fortgeschritten ist, zwischen 0 und 1
+    fn get_fortschritt(&self) -> (usize, f64) {
+        let schritte = self.verstrichen.as_secs_f64() / self.dauer_pro_schritt.as_secs_f64();
+        let schritt = (schritte as usize).min(self.get_schritte() - 1);
+        (schritt, (schritte - schritt as f64).min(1.0))
+    }
+}
+
+/// Wer die Züge einer Seite macht
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Steuerung {
+    Mensch,
+    Engine,
+    /// Der Gegner in einer Netzpartie
+    Netzwerk,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum EditorAktion {
+    Figur(Feld),
+    Leeren,
+    Startaufstellung,
+    AmZug,
+    Spielen,
+    Analysieren,
+}
+
+/// Ein anklickbarer Bereich in der Seitenleiste
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct Knopf {
+    x: usize,
+    y: usize,
+    breite: usize,
+    hoehe: usize,
+    aktion: EditorAktion,
+}
+
+impl Knopf {
+    fn enthaelt(&self, x: f32, y: f32) -> bool {
+        x >= self.x as f32
+            && y >= self.y as f32
+            && x < (self.x + self.breite) as f32
+            && y < (self.y + self.hoehe) as f32
+    }
+}
+
+/// Der Zustand beim Aufbauen einer Stellung
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct Editor {
+    /// Die Figur, die mit der Maus gesetzt wird
+    figur: Feld,
+    am_zug: Spieler,
+}
 
 struct Application {
     brett: SpielBrett,
+    /// Die Partie seit der letzten Bearbeitung des Bretts, für die Remisregeln
+    partie: Partie,
+    buch: Eroeffnungsbuch,
+    datenbank: Option<Endspieldatenbank>,
+    gewichte: Gewichte,
+    zufall: Zufall,
+    /// Die Analyse gilt nur, solange das Brett unverändert ist
+    analyse: Option<(SpielBrett, Vec<ZugAnalyse>)>,
+    /// Der beste Zug für den Menschen am Zug, solange das Brett unverändert ist
+    hinweis: Option<(SpielBrett, Zug)>,
+    /// Der ausgewählte Stein des Menschen am Zug
+    /// Bei einem Mehrfachschlag folgen die Felder, auf die er schon gezogen wurde
+    auswahl: Vec<Position>,
+    /// Der zuletzt gespielte Zug, solange das Brett danach unverändert ist
+    letzter_zug: Option<(SpielBrett, Zug)>,
+    maus_gedrueckt: bool,
+    /// Wo die ausgewählte Figur gezeichnet wird, solange sie mit der Maus gezogen wird
+    gezogen: Option<(f32, f32)>,
+    /// Die Züge seit der letzten Bearbeitung des Bretts in Notation
+    zuege: Vec<(Spieler, String)>,
+    /// Die Bewertung der letzten Suche aus Sicht des Spielers, der gezogen hat, und ihre Tiefe
+    bewertung: Option<(Spieler, i32, u32)>,
+    animation: Option<Animation>,
+    animations_tempo: AnimationsTempo,
+    steuerung_schwarz: Steuerung,
+    steuerung_weiss: Steuerung,
+    /// Die Seite, die der Mensch gegen die Engine spielt
+    mensch_seite: Spieler,
+    /// Wer in einem neuen Spiel den ersten Zug macht
+    beginner: Spieler,
+    /// Die Bewertung der Stellung aus Sicht von Schwarz für den Bewertungsbalken, solange das
+    /// Brett unverändert ist
+    balken: Option<(SpielBrett, Spieler, i32)>,
+    zeige_balken: bool,
+    /// Solange eine Stellung aufgebaut wird, wird nicht gespielt
+    editor: Option<Editor>,
+    /// Die Bedenkzeit für neue Spiele
+    zeitkontrolle: Option<Zeitkontrolle>,
+    uhr: Option<Uhr>,
+    letzte_zeit: Instant,
+    lautsprecher: Lautsprecher,
+    ton_an: bool,
+    netz: Option<Netzpartie>,
+    /// Ein Ende, das nicht auf dem Brett entschieden wurde, wie Aufgabe oder vereinbartes Remis
+    ende: Option<Spielstand>,
+    /// Ob der Gegner im Netzwerk Remis angeboten hat
+    remis_angebot: bool,
+    /// Die letzte Meldung in der Seitenleiste, etwa vom Netzwerk
+    meldung: Option<String>,
     window: Window,
-    buffer: Vec<Color>,
+    leinwand: Leinwand,
+    layout: Layout,
+}
+
+/// Wo Brett und Seitenleiste in einem Fenster der Größe `breite` mal `hoehe` liegen
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct Layout {
+    breite: usize,
+    hoehe: usize,
+    feld_size: usize,
+    brett_x: usize,
+    brett_y: usize,
+    panel_x: usize,
+    /// Ob das Brett um 180 Grad gedreht gezeigt wird, sodass Schwarz unten sitzt
+    gedreht: bool,
 }
 
-fn get_feld_color(feld: Feld) -> Color {
-    match feld {
-        Feld::Leer => BLACK,
-        Feld::Stein(Spieler::Mensch) => LIGHT_RED,
-        Feld::Stein(Spieler::Computer) => LIGHT_BLUE,
-        Feld::Dame(Spieler::Mensch) => DARK_RED,
-        Feld::Dame(Spieler::Computer) => DARK_BLUE,
+impl Layout {
+    fn new(breite: usize, hoehe: usize, gedreht: bool) -> Self {
+        let panel_breite = PANEL_BREITE.min(breite / 3);
+        let feld_size = (hoehe.min(breite - panel_breite) / SpielBrett::SIZE).max(1);
+        let brett_size = feld_size * SpielBrett::SIZE;
+        Self {
+            breite,
+            hoehe,
+            feld_size,
+            brett_x: (breite - panel_breite).saturating_sub(brett_size) / 2,
+            brett_y: hoehe.saturating_sub(brett_size) / 2,
+            panel_x: breite - panel_breite,
+            gedreht,
+        }
+    }
+
+    /// Wechselt zwischen einem Feld und dem Feld, an dem es im Fenster gezeigt wird
+    fn drehe(&self, position: Position) -> Position {
+        if self.gedreht {
+            Position {
+                spalte: SpielBrett::SIZE - 1 - position.spalte,
+                zeile: SpielBrett::SIZE - 1 - position.zeile,
+            }
+        } else {
+            position
+        }
+    }
+
+    /// Die obere linke Ecke eines Feldes in Pixeln
+    fn get_ecke(&self, position: Position) -> (usize, usize) {
+        let position = self.drehe(position);
+        (
+            self.brett_x + position.spalte * self.feld_size,
+            self.brett_y + position.zeile * self.feld_size,
+        )
+    }
+
+    /// Der Mittelpunkt eines Feldes in Pixeln
+    fn get_mitte(&self, position: Position) -> (f64, f64) {
+        let (x, y) = self.get_ecke(position);
+        (
+            x as f64 + self.feld_size as f64 / 2.0,
+            y as f64 + self.feld_size as f64 / 2.0,
+        )
+    }
+
+    /// Das Feld unter einem Pixel, falls dort eines ist
+    fn get_position(&self, x: f32, y: f32) -> Option<Position> {
+        if x < self.brett_x as f32 || y < self.brett_y as f32 {
+            return None;
+        }
+        let position = Position {
+            spalte: (x as usize - self.brett_x) / self.feld_size,
+            zeile: (y as usize - self.brett_y) / self.feld_size,
+        };
+        (position.spalte < SpielBrett::SIZE && position.zeile < SpielBrett::SIZE)
+            .then(|| self.drehe(position))
+            .filter(|position| position.valid())
     }
 }
 
 impl Application {
     fn open() -> Option<Self> {
         Some(Application {
-            brett: SpielBrett::parse(concat!(
-                "m m m m \n",
-                " m m m m\n",
-                "m m m m \n",
-                " _ _ _ _\n",
-                "_ _ _ _ \n",
-                " c c c c\n",
-                "c c c c \n",
-                " c c c c",
-            ))
-            .unwrap(),
-            buffer: vec![0; SIZE * SIZE],
+            brett: SpielBrett::startaufstellung(),
+            partie: Partie::new(
+                SpielBrett::startaufstellung(),
+                Spieler::Schwarz,
+                Remisregeln::default(),
+            ),
+            buch: Eroeffnungsbuch::load(EROEFFNUNGSBUCH_DATEI).unwrap_or_default(),
+            datenbank: Endspieldatenbank::load(ENDSPIELDATENBANK_DATEI),
+            gewichte: Gewichte::load(GEWICHTE_DATEI).unwrap_or_default(),
+            zufall: Zufall::from_zeit(),
+            analyse: None,
+            hinweis: None,
+            auswahl: Vec::new(),
+            letzter_zug: None,
+            maus_gedrueckt: false,
+            gezogen: None,
+            zuege: Vec::new(),
+            bewertung: None,
+            animation: None,
+            animations_tempo: AnimationsTempo::Normal,
+            steuerung_schwarz: Steuerung::Mensch,
+            steuerung_weiss: Steuerung::Engine,
+            mensch_seite: Spieler::Schwarz,
+            beginner: Spieler::Schwarz,
+            balken: None,
+            zeige_balken: false,
+            editor: None,
+            zeitkontrolle: None,
+            uhr: None,
+            letzte_zeit: Instant::now(),
+            lautsprecher: Lautsprecher::open(),
+            ton_an: true,
+            netz: None,
+            ende: None,
+            remis_angebot: false,
+            meldung: None,
+            leinwand: Leinwand::new(SIZE + PANEL_BREITE, SIZE),
+            layout: Layout::new(SIZE + PANEL_BREITE, SIZE, false),
             window: {
-                let mut window = match Window::new("Dame", SIZE, SIZE, WindowOptions::default()) {
+                let options = WindowOptions {
+                    resize: true,
+                    scale_mode: ScaleMode::UpperLeft,
+                    ..WindowOptions::default()
+                };
+                let mut window = match Window::new("Dame", SIZE + PANEL_BREITE, SIZE, options) {
                     Ok(window) => window,
                     Err(_) => return None,
                 };
@@ -62,103 +356,1557 @@ impl Application {
         })
     }
 
-    fn set_pixel(&mut self, x: usize, y: usize, color: Color) {
-        self.buffer[y * SIZE + x] = color;
-    }
-
-    fn draw_rect(
-        &mut self,
-        x_start: usize,
-        y_start: usize,
-        width: usize,
-        height: usize,
-        color: Color,
-    ) {
-        for x in x_start..x_start + width {
-            for y in y_start..y_start + height {
-                self.set_pixel(x, y, color);
-            }
+    /// Passt Leinwand und Layout an, wenn sich die Größe des Fensters geändert hat
+    fn update_size(&mut self) {
+        let (breite, hoehe) = self.window.get_size();
+        let (breite, hoehe) = (
+            breite.max(SpielBrett::SIZE * 3),
+            hoehe.max(SpielBrett::SIZE),
+        );
+        if (breite, hoehe) != (self.layout.breite, self.layout.hoehe) {
+            self.leinwand = Leinwand::new(breite, hoehe);
+            self.layout = Layout::new(breite, hoehe, self.layout.gedreht);
         }
     }
 
     fn draw(&mut self) {
+        self.leinwand.fill(PANEL);
+        let feld_size = self.layout.feld_size;
+        let bewegt = self.get_bewegte_figur();
+        let geschlagen = self.get_auswahl_geschlagen();
         for zeile in 0..SpielBrett::SIZE {
             for spalte in 0..SpielBrett::SIZE {
                 let position = Position { spalte, zeile };
+                let (x, y) = self.layout.get_ecke(position);
                 if !position.valid() {
-                    self.draw_rect(
-                        spalte * FELD_SIZE,
-                        zeile * FELD_SIZE,
-                        FELD_SIZE,
-                        FELD_SIZE,
-                        WHITE,
-                    );
+                    self.leinwand
+                        .draw_rect(x, y, feld_size, feld_size, HELLES_FELD);
                     continue;
                 }
-                self.draw_rect(
-                    spalte * FELD_SIZE,
-                    zeile * FELD_SIZE,
-                    FELD_SIZE,
-                    FELD_SIZE,
-                    BLACK,
+                let letzter_zug = match &self.letzter_zug {
+                    Some((brett, zug)) if *brett == self.brett => {
+                        zug.von() == position || zug.nach() == position
+                    }
+                    _ => false,
+                };
+                self.leinwand.draw_rect(
+                    x,
+                    y,
+                    feld_size,
+                    feld_size,
+                    if letzter_zug {
+                        LETZTER_ZUG
+                    } else {
+                        DUNKLES_FELD
+                    },
                 );
-                self.draw_rect(
-                    spalte * FELD_SIZE + 15,
-                    zeile * FELD_SIZE + 15,
-                    FELD_SIZE - 30,
-                    FELD_SIZE - 30,
-                    get_feld_color(self.brett.get(position)),
+                // Bei kleinen Feldern würden die Nummern die Figuren verdecken
+                if feld_size >= 40 {
+                    self.leinwand.draw_text(
+                        x + feld_size / 25,
+                        y + feld_size / 25,
+                        &position.to_feld_nummer().to_string(),
+                        1,
+                        helligkeit(DUNKLES_FELD, 0.7),
+                    );
+                }
+                let ist_bewegt = bewegt.is_some() && self.auswahl.first() == Some(&position);
+                if self.animation.is_none() && !ist_bewegt {
+                    let (x, y) = self.layout.get_mitte(position);
+                    self.leinwand
+                        .set_deckung(if geschlagen.contains(&position) {
+                            0.4
+                        } else {
+                            1.0
+                        });
+                    self.draw_figur(x, y, self.brett.get(position));
+                }
+            }
+        }
+        self.leinwand.set_deckung(1.0);
+
+        self.draw_animation();
+        self.draw_analyse();
+        self.draw_hinweis();
+        self.draw_auswahl();
+        if let Some((x, y)) = bewegt {
+            self.draw_figur(x, y, self.brett.get(self.auswahl[0]));
+        }
+        self.draw_panel();
+    }
+
+    fn get_spieler_color(&self, spieler: Spieler) -> Color {
+        match spieler {
+            Spieler::Schwarz => DUNKLE_FIGUR,
+            Spieler::Weiss => HELLE_FIGUR,
+        }
+    }
+
+    fn get_name(&self, spieler: Spieler) -> &'static str {
+        match spieler {
+            Spieler::Schwarz => "Schwarz",
+            Spieler::Weiss => "Weiß",
+        }
+    }
+
+    fn get_steuerung(&self, spieler: Spieler) -> Steuerung {
+        match spieler {
+            Spieler::Schwarz => self.steuerung_schwarz,
+            Spieler::Weiss => self.steuerung_weiss,
+        }
+    }
+
+    fn set_steuerung(&mut self, schwarz: Steuerung, weiss: Steuerung) {
+        self.steuerung_schwarz = schwarz;
+        self.steuerung_weiss = weiss;
+        self.auswahl.clear();
+    }
+
+    /// Der Mensch spielt `mensch_seite`, die Engine die andere Seite
+    fn spiele_gegen_engine(&mut self) {
+        match self.mensch_seite {
+            Spieler::Schwarz => self.set_steuerung(Steuerung::Mensch, Steuerung::Engine),
+            Spieler::Weiss => self.set_steuerung(Steuerung::Engine, Steuerung::Mensch),
+        }
+    }
+
+    /// Der Mensch spielt `farbe`, der Gegner im Netzwerk die andere Seite
+    fn spiele_gegen_netz(&mut self, farbe: Spieler) {
+        self.mensch_seite = farbe;
+        match farbe {
+            Spieler::Schwarz => self.set_steuerung(Steuerung::Mensch, Steuerung::Netzwerk),
+            Spieler::Weiss => self.set_steuerung(Steuerung::Netzwerk, Steuerung::Mensch),
+        }
+    }
+
+    /// Wessen Figuren mit der Maus gezogen werden, wenn ein Mensch am Zug ist
+    fn get_maus_spieler(&self) -> Option<Spieler> {
+        let am_zug = self.get_aktuelle_partie().get_am_zug();
+        (self.get_steuerung(am_zug) == Steuerung::Mensch).then_some(am_zug)
+    }
+
+    /// Die Partie, wenn sie zum Brett passt, sonst eine neue Partie mit Schwarz am Zug
+    fn get_aktuelle_partie(&self) -> Partie {
+        if *self.partie.get_brett() == self.brett {
+            self.partie.clone()
+        } else {
+            Partie::new(self.brett.clone(), Spieler::Schwarz, Remisregeln::default())
+        }
+    }
+
+    /// In kleinen Fenstern wird die Schrift kleiner
+    fn get_skala(&self) -> usize {
+        if self.layout.hoehe >= 500 {
+            2
+        } else {
+            1
+        }
+    }
+
+    /// Zeichnet die Hilfe unten in der Seitenleiste und gibt zurück, wo sie beginnt
+    fn draw_hilfe(&mut self, hilfe: &[&str]) -> usize {
+        let x = self.layout.panel_x + PANEL_RAND;
+        let hilfe_y = self
+            .layout
+            .hoehe
+            .saturating_sub(PANEL_RAND + hilfe.len() * Leinwand::get_zeilen_hoehe(1));
+        for (index, text) in hilfe.iter().enumerate() {
+            self.leinwand.draw_text(
+                x,
+                hilfe_y + index * Leinwand::get_zeilen_hoehe(1),
+                text,
+                1,
+                TEXT_GRAU,
+            );
+        }
+        hilfe_y
+    }
+
+    /// Wessen Zeit abgelaufen ist. Über die Zeit des Gegners im Netzwerk entscheidet nur seine
+    /// eigene Uhr, er meldet den Zeitverlust selbst.
+    fn get_zeitverlust(&self) -> Option<Spieler> {
+        let uhr = self.uhr?;
+        [Spieler::Schwarz, Spieler::Weiss]
+            .into_iter()
+            .find(|&spieler| {
+                uhr.is_abgelaufen(spieler) && self.get_steuerung(spieler) != Steuerung::Netzwerk
+            })
+    }
+
+    /// Der Spielstand der Partie, bei abgelaufener Zeit hat der Gegner gewonnen
+    fn get_spielstand(&self) -> Spielstand {
+        if let Some(ende) = self.ende {
+            return ende;
+        }
+        match self.get_zeitverlust() {
+            Some(spieler) => Spielstand::Gewonnen(!spieler),
+            None => self.get_aktuelle_partie().get_spielstand(),
+        }
+    }
+
+    fn draw_panel(&mut self) {
+        let x = self.layout.panel_x + PANEL_RAND;
+        let mut y = PANEL_RAND;
+        let skala = self.get_skala();
+        let zeile = Leinwand::get_zeilen_hoehe(skala);
+
+        if let Some(editor) = self.editor {
+            self.leinwand.draw_text(x, y, "Aufbauen", skala + 1, TEXT);
+            self.draw_editor(editor);
+            return;
+        }
+        self.leinwand.draw_text(x, y, "Dame", skala + 1, TEXT);
+        y += Leinwand::get_zeilen_hoehe(skala + 1) + PANEL_RAND / 2;
+
+        let partie = self.get_aktuelle_partie();
+        let status = match self.get_spielstand() {
+            Spielstand::Laeuft => format!("Am Zug: {}", self.get_name(partie.get_am_zug())),
+            Spielstand::Gewonnen(spieler) if self.get_zeitverlust().is_some() => {
+                format!("{} gewinnt (Zeit)", self.get_name(spieler))
+            }
+            Spielstand::Gewonnen(spieler) if self.ende.is_some() => {
+                format!("{} gewinnt (Aufgabe)", self.get_name(spieler))
+            }
+            Spielstand::Gewonnen(spieler) => format!("{} gewinnt", self.get_name(spieler)),
+            Spielstand::Remis(grund) => format!("Remis: {grund:?}"),
+        };
+        self.leinwand.draw_text(x, y, &status, skala, TEXT);
+        y += zeile;
+
+        if let Some(uhr) = self.uhr {
+            for spieler in [Spieler::Schwarz, Spieler::Weiss] {
+                let text = format!(
+                    "{} {}",
+                    self.get_name(spieler),
+                    format_zeit(uhr.get_restzeit(spieler))
                 );
+                let color = if spieler == partie.get_am_zug() {
+                    TEXT
+                } else {
+                    TEXT_GRAU
+                };
+                self.leinwand.draw_text(x, y, &text, skala, color);
+                y += zeile;
+            }
+        }
+
+        let bewertung = match self.bewertung {
+            // Züge aus Eröffnungsbuch und Endspieldatenbank haben die Tiefe 0
+            Some((spieler, bewertung, 0)) => format!("{}: {bewertung:+}", self.get_name(spieler)),
+            Some((spieler, bewertung, depth)) => {
+                format!("{}: {bewertung:+}, Tiefe {depth}", self.get_name(spieler))
+            }
+            None => "-".to_string(),
+        };
+        self.leinwand
+            .draw_text(x, y, &format!("Bewertung {bewertung}"), 1, TEXT_GRAU);
+        y += Leinwand::get_zeilen_hoehe(1);
+        if self.zeige_balken {
+            y += self.draw_balken(x, y);
+        }
+        let einstellungen = match (self.steuerung_schwarz, self.steuerung_weiss) {
+            _ if self.netz.as_ref().is_some_and(|netz| !netz.is_verbunden()) => {
+                "Netzwerk: warte auf Gegner".to_string()
+            }
+            (Steuerung::Netzwerk, _) | (_, Steuerung::Netzwerk) => {
+                format!("Netzwerk: du spielst {}", self.get_name(self.mensch_seite))
+            }
+            (Steuerung::Mensch, Steuerung::Mensch) => {
+                format!("Zwei Spieler, {} beginnt", self.get_name(self.beginner))
+            }
+            (Steuerung::Engine, Steuerung::Engine) => "Engine gegen Engine".to_string(),
+            _ => format!(
+                "Du spielst {}, {} beginnt",
+                self.get_name(self.mensch_seite),
+                self.get_name(self.beginner)
+            ),
+        };
+        self.leinwand.draw_text(x, y, &einstellungen, 1, TEXT_GRAU);
+        y += Leinwand::get_zeilen_hoehe(1);
+        if self.remis_angebot {
+            self.leinwand
+                .draw_text(x, y, "Remis angeboten: R/K", 1, ORANGE);
+            y += Leinwand::get_zeilen_hoehe(1);
+        }
+        if let Some(meldung) = &self.meldung {
+            self.leinwand.draw_text(x, y, meldung, 1, TEXT);
+            y += Leinwand::get_zeilen_hoehe(1);
+        }
+        let bedenkzeit = match self.zeitkontrolle {
+            Some(kontrolle) => format!("Bedenkzeit {kontrolle}"),
+            None => "Ohne Bedenkzeit".to_string(),
+        };
+        self.leinwand.draw_text(x, y, &bedenkzeit, 1, TEXT_GRAU);
+        y += zeile + PANEL_RAND / 2;
+
+        // Zugliste mit einem Zugpaar pro Zeile, von unten her gekürzt
+        let mut zeilen: Vec<String> = Vec::new();
+        for (index, (spieler, notation)) in self.zuege.iter().enumerate() {
+            match spieler {
+                Spieler::Weiss if index > 0 => {
+                    zeilen.last_mut().unwrap().push_str(&format!(" {notation}"));
+                }
+                Spieler::Weiss => zeilen.push(format!("1. ... {notation}")),
+                Spieler::Schwarz => zeilen.push(format!("{}. {notation}", zeilen.len() + 1)),
+            }
+        }
+        let hilfe: &[&str] = if self.netz.is_some() {
+            &[
+                "Maus: Stein ziehen",
+                "H: Hinweis  A: Analyse",
+                "G: Aufgeben",
+                "R: Remis anbieten/annehmen",
+                "K: Remis ablehnen",
+                "T: Animation schneller/aus",
+                "L: Ton an/aus  V: Bewertungsbalken",
+                "D: Brett drehen",
+            ]
+        } else {
+            &[
+                "Maus: Stein ziehen",
+                "Leertaste: Engine zieht",
+                "H: Hinweis  A: Analyse",
+                "E: Engine gegen Engine",
+                "S: Stellung aufbauen",
+                "T: Animation schneller/aus",
+                "L: Ton an/aus  V: Bewertungsbalken",
+                "2: Zwei Spieler an/aus",
+                "D: Brett drehen  F: Seite wechseln",
+                "B: Wer beginnt  N: Neues Spiel",
+                "Z: Bedenkzeit wechseln",
+            ]
+        };
+        let hilfe_y = self.draw_hilfe(hilfe);
+        let platz = hilfe_y.saturating_sub(PANEL_RAND + y) / zeile;
+        for text in &zeilen[zeilen.len().saturating_sub(platz)..] {
+            self.leinwand.draw_text(x, y, text, skala, TEXT);
+            y += zeile;
+        }
+    }
+
+    /// Zeichnet den Anteil, den die Bewertung jeder Seite gibt, und gibt die Höhe zurück
+    fn draw_balken(&mut self, x: usize, y: usize) -> usize {
+        let Some((brett, spieler, bewertung)) = &self.balken else {
+            return 0;
+        };
+        if *brett != self.brett || *spieler != self.get_aktuelle_partie().get_am_zug() {
+            return 0;
+        }
+        let breite = self.layout.breite.saturating_sub(x + PANEL_RAND);
+        let hoehe = 4 * self.get_skala();
+        // Ein Stein Vorsprung entspricht etwa 64 Prozent
+        let anteil = 1.0 / (1.0 + 10f64.powf(-(*bewertung as f64) / 400.0));
+        let schwarz_breite = (breite as f64 * anteil).round() as usize;
+        let (schwarz, weiss) = (
+            self.get_spieler_color(Spieler::Schwarz),
+            self.get_spieler_color(Spieler::Weiss),
+        );
+        self.leinwand
+            .draw_rect(x, y, schwarz_breite, hoehe, schwarz);
+        self.leinwand
+            .draw_rect(x + schwarz_breite, y, breite - schwarz_breite, hoehe, weiss);
+        self.leinwand.draw_rect(x + breite / 2, y, 1, hoehe, ORANGE);
+        hoehe + 4
+    }
+
+    /// Bewertet die Stellung für den Bewertungsbalken neu, wenn sich das Brett geändert hat
+    fn update_balken(&mut self) {
+        if !self.zeige_balken || self.animation.is_some() || self.editor.is_some() {
+            return;
+        }
+        let partie = self.get_aktuelle_partie();
+        let am_zug = partie.get_am_zug();
+        if matches!(&self.balken, Some((brett, spieler, _)) if *brett == self.brett && *spieler == am_zug)
+        {
+            return;
+        }
+        let bewertung = get_balken_bewertung(&partie, self.gewichte, self.datenbank.as_ref());
+        self.balken = Some((self.brett.clone(), am_zug, bewertung));
+    }
+
+    /// Die Knöpfe in der Seitenleiste, solange eine Stellung aufgebaut wird
+    fn get_editor_knoepfe(&self) -> Vec<Knopf> {
+        let skala = self.get_skala();
+        let x = self.layout.panel_x + PANEL_RAND;
+        let breite = self.layout.breite.saturating_sub(x + PANEL_RAND);
+        let mut y = PANEL_RAND + Leinwand::get_zeilen_hoehe(skala + 1) + PANEL_RAND / 2;
+        let mut knoepfe = Vec::new();
+
+        let figuren = [
+            Feld::Stein(Spieler::Schwarz),
+            Feld::Dame(Spieler::Schwarz),
+            Feld::Stein(Spieler::Weiss),
+            Feld::Dame(Spieler::Weiss),
+            Feld::Leer,
+        ];
+        let size = (breite / figuren.len()).min(24 * skala + 8);
+        for (index, figur) in figuren.into_iter().enumerate() {
+            knoepfe.push(Knopf {
+                x: x + index * size,
+                y,
+                breite: size,
+                hoehe: size,
+                aktion: EditorAktion::Figur(figur),
+            });
+        }
+        y += size + PANEL_RAND / 2;
+
+        let hoehe = Leinwand::get_zeilen_hoehe(skala) + 2 * skala;
+        for aktion in [
+            EditorAktion::Leeren,
+            EditorAktion::Startaufstellung,
+            EditorAktion::AmZug,
+            EditorAktion::Spielen,
+            EditorAktion::Analysieren,
+        ] {
+            knoepfe.push(Knopf {
+                x,
+                y,
+                breite,
+                hoehe,
+                aktion,
+            });
+            y += hoehe + 4 * skala;
+        }
+        knoepfe
+    }
+
+    fn draw_editor(&mut self, editor: Editor) {
+        let skala = self.get_skala();
+        let knoepfe = self.get_editor_knoepfe();
+        for knopf in &knoepfe {
+            let color = if knopf.aktion == EditorAktion::Figur(editor.figur) {
+                KNOPF_AKTIV
+            } else {
+                KNOPF
+            };
+            self.leinwand.draw_rect(
+                knopf.x + 1,
+                knopf.y + 1,
+                knopf.breite.saturating_sub(2),
+                knopf.hoehe.saturating_sub(2),
+                color,
+            );
+            let text = match knopf.aktion {
+                EditorAktion::Figur(Feld::Leer) => "X".to_string(),
+                EditorAktion::Figur(figur) => {
+                    let radius = knopf.breite as f64 * 0.35;
+                    let (mitte_x, mitte_y) = (
+                        knopf.x as f64 + knopf.breite as f64 / 2.0,
+                        knopf.y as f64 + knopf.hoehe as f64 / 2.0,
+                    );
+                    self.draw_figur_mit_radius(mitte_x, mitte_y, radius, figur);
+                    continue;
+                }
+                EditorAktion::Leeren => "Brett leeren".to_string(),
+                EditorAktion::Startaufstellung => "Startaufstellung".to_string(),
+                EditorAktion::AmZug => format!("Am Zug: {}", self.get_name(editor.am_zug)),
+                EditorAktion::Spielen => "Spielen".to_string(),
+                EditorAktion::Analysieren => "Analysieren".to_string(),
+            };
+            let text_x = knopf.x
+                + knopf
+                    .breite
+                    .saturating_sub(Leinwand::get_text_breite(&text, skala))
+                    / 2;
+            let text_y = knopf.y + (knopf.hoehe - 7 * skala) / 2;
+            self.leinwand.draw_text(text_x, text_y, &text, skala, TEXT);
+        }
+
+        let x = self.layout.panel_x + PANEL_RAND;
+        let mut y = knoepfe
+            .last()
+            .map_or(0, |knopf| knopf.y + knopf.hoehe + PANEL_RAND / 2);
+        let fehler = self.brett.pruefe_aufstellung(editor.am_zug);
+        if fehler.is_empty() {
+            self.leinwand
+                .draw_text(x, y, "Stellung in Ordnung", 1, TEXT_GRAU);
+        }
+        for fehler in fehler {
+            self.leinwand
+                .draw_text(x, y, &fehler.to_string(), 1, ORANGE);
+            y += Leinwand::get_zeilen_hoehe(1);
+        }
+
+        self.draw_hilfe(&[
+            "Maus links: Figur setzen",
+            "Maus rechts: Feld leeren",
+            "M/C: Schwarz/Weiß, Shift: Dame",
+            "Rück: Feld leeren",
+            "S: Spielen",
+        ]);
+    }
+
+    fn draw_animation(&mut self) {
+        let Some(animation) = &self.animation else {
+            return;
+        };
+        let (schritt, anteil) = animation.get_fortschritt();
+        // Langsam anfahren und abbremsen
+        let anteil = anteil * anteil * (3.0 - 2.0 * anteil);
+        let vorher = animation.vorher.clone();
+        let zug = animation.zug.clone();
+
+        for zeile in 0..SpielBrett::SIZE {
+            for spalte in 0..SpielBrett::SIZE {
+                let position = Position { spalte, zeile };
+                if !position.valid() || position == zug.von() {
+                    continue;
+                }
+                let deckung = match zug.geschlagen.iter().position(|&p| p == position) {
+                    Some(index) if index < schritt => 0.0,
+                    Some(index) if index == schritt => 1.0 - anteil,
+                    _ => 1.0,
+                };
+                let (x, y) = self.layout.get_mitte(position);
+                self.leinwand.set_deckung(deckung);
+                self.draw_figur(x, y, vorher.get(position));
+            }
+        }
+        self.leinwand.set_deckung(1.0);
+
+        let (x_von, y_von) = self.layout.get_mitte(zug.pfad[schritt]);
+        let (x_nach, y_nach) = self.layout.get_mitte(zug.pfad[schritt + 1]);
+        self.draw_figur(
+            x_von + (x_nach - x_von) * anteil,
+            y_von + (y_nach - y_von) * anteil,
+            vorher.get(zug.von()),
+        );
+    }
+
+    /// Lässt Animation und Uhr um die Zeit seit dem letzten Aufruf weiterlaufen
+    fn update_zeit(&mut self) {
+        let jetzt = Instant::now();
+        let vergangen = jetzt - self.letzte_zeit;
+        self.letzte_zeit = jetzt;
+        self.update_animation(vergangen);
+
+        if self.editor.is_some() || self.get_spielstand() != Spielstand::Laeuft {
+            return;
+        }
+        let am_zug = self.get_aktuelle_partie().get_am_zug();
+        if let Some(uhr) = &mut self.uhr {
+            uhr.vergehe(am_zug, vergangen);
+        }
+        if self.get_zeitverlust() == Some(am_zug) {
+            if let Some(netz) = &mut self.netz {
+                netz.melde_zeitverlust();
+            }
+            self.spiele_spielende();
+        }
+    }
+
+    fn spiele_geraeusch(&self, geraeusch: Geraeusch) {
+        if self.ton_an {
+            self.lautsprecher.spiele(geraeusch);
+        }
+    }
+
+    /// Spielt eine Melodie, wenn die Partie gewonnen ist. Verliert ein Mensch gegen die Engine,
+    /// klingt sie traurig.
+    fn spiele_spielende(&self) {
+        if let Spielstand::Gewonnen(gewinner) = self.get_spielstand() {
+            if self.get_steuerung(gewinner) == Steuerung::Engine
+                && self.get_steuerung(!gewinner) == Steuerung::Mensch
+            {
+                self.spiele_geraeusch(Geraeusch::Niederlage);
+            } else {
+                self.spiele_geraeusch(Geraeusch::Sieg);
+            }
+        }
+    }
+
+    fn update_animation(&mut self, vergangen: Duration) {
+        if let Some(animation) = &mut self.animation {
+            animation.verstrichen += vergangen;
+            if animation.is_fertig() {
+                self.animation = None;
+            }
+        }
+    }
+
+    /// Zeichnet eine Figur mit ihrem Mittelpunkt bei `(x, y)`
+    fn draw_figur(&mut self, x: f64, y: f64, feld: Feld) {
+        let radius = self.layout.feld_size as f64 * 0.38;
+        self.draw_figur_mit_radius(x, y, radius, feld);
+    }
+
+    fn draw_figur_mit_radius(&mut self, x: f64, y: f64, radius: f64, feld: Feld) {
+        let (spieler, dame) = match feld {
+            Feld::Leer => return,
+            Feld::Stein(spieler) => (spieler, false),
+            Feld::Dame(spieler) => (spieler, true),
+        };
+        let color = self.get_spieler_color(spieler);
+
+        self.leinwand
+            .draw_kreis(x + radius * 0.08, y + radius * 0.12, radius, BLACK, 0.35);
+        self.leinwand.draw_kugel(x, y, radius, color);
+        let rille = if dame { KRONE } else { helligkeit(color, 0.8) };
+        self.leinwand
+            .draw_ring(x, y, radius * 0.7, radius * 0.78, rille);
+
+        if dame {
+            let punkte = [
+                (-0.45, 0.28),
+                (-0.5, -0.3),
+                (-0.22, -0.02),
+                (0.0, -0.42),
+                (0.22, -0.02),
+                (0.5, -0.3),
+                (0.45, 0.28),
+            ]
+            .map(|(dx, dy)| (x + dx * radius, y + dy * radius));
+            self.leinwand.draw_polygon(&punkte, KRONE);
+            for (spitze_x, spitze_y) in [punkte[1], punkte[3], punkte[5]] {
+                self.leinwand
+                    .draw_kreis(spitze_x, spitze_y, radius * 0.08, KRONE, 1.0);
             }
         }
     }
 
+    fn draw_rahmen(&mut self, position: Position, color: Color) {
+        let (x, y) = self.layout.get_ecke(position);
+        let size = self.layout.feld_size;
+        let dicke = (size / 16).max(1);
+        self.leinwand.draw_rect(x, y, size, dicke, color);
+        self.leinwand
+            .draw_rect(x, y + size - dicke, size, dicke, color);
+        self.leinwand.draw_rect(x, y, dicke, size, color);
+        self.leinwand
+            .draw_rect(x + size - dicke, y, dicke, size, color);
+    }
+
+    /// Zeichnet eine Linie zwischen den Mittelpunkten zweier Felder
+    fn draw_linie(&mut self, von: Position, nach: Position, color: Color) {
+        let dicke = self.layout.feld_size as f64 * 0.06;
+        let ((x_von, y_von), (x_nach, y_nach)) =
+            (self.layout.get_mitte(von), self.layout.get_mitte(nach));
+        let laenge = (x_nach - x_von).hypot(y_nach - y_von).max(1.0);
+        // Senkrecht zur Linie um die halbe Dicke verschoben
+        let (nx, ny) = (
+            -(y_nach - y_von) / laenge * dicke / 2.0,
+            (x_nach - x_von) / laenge * dicke / 2.0,
+        );
+        self.leinwand.draw_polygon(
+            &[
+                (x_von + nx, y_von + ny),
+                (x_nach + nx, y_nach + ny),
+                (x_nach - nx, y_nach - ny),
+                (x_von - nx, y_von - ny),
+            ],
+            color,
+        );
+        self.leinwand.draw_kreis(x_nach, y_nach, dicke, color, 1.0);
+    }
+
+    /// Die Züge, die mit den bisher ausgewählten Feldern beginnen
+    fn get_auswahl_zuege(&self) -> Vec<(Zug, SpielBrett)> {
+        let Some(am_zug) = self.get_maus_spieler() else {
+            return Vec::new();
+        };
+        if self.auswahl.is_empty() {
+            return Vec::new();
+        }
+        self.brett
+            .get_possible_zuege(am_zug)
+            .into_iter()
+            .filter(|(zug, _)| zug.pfad.starts_with(&self.auswahl))
+            .collect()
+    }
+
+    /// Die Steine, die bei einem angefangenen Mehrfachschlag schon übersprungen wurden
+    fn get_auswahl_geschlagen(&self) -> Vec<Position> {
+        match self.get_auswahl_zuege().first() {
+            Some((zug, _)) => zug.geschlagen[..self.auswahl.len() - 1].to_vec(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Wo die ausgewählte Figur gezeichnet wird, wenn sie nicht auf ihrem Feld steht
+    fn get_bewegte_figur(&self) -> Option<(f64, f64)> {
+        if let Some((x, y)) = self.gezogen {
+            return Some((x as f64, y as f64));
+        }
+        match self.auswahl.as_slice() {
+            [_, .., letzte] => Some(self.layout.get_mitte(*letzte)),
+            _ => None,
+        }
+    }
+
+    fn draw_auswahl(&mut self) {
+        let auswahl = match self.auswahl.first() {
+            Some(&auswahl)
+                if self
+                    .get_maus_spieler()
+                    .is_some_and(|am_zug| self.brett.get(auswahl).is_spieler(am_zug)) =>
+            {
+                self.auswahl[self.auswahl.len() - 1]
+            }
+            _ => return,
+        };
+        self.draw_rahmen(auswahl, GREEN);
+        let schritte = self.auswahl.len() - 1;
+        for (zug, _) in self.get_auswahl_zuege() {
+            if zug.is_schlagen() {
+                for schritt in zug.pfad[schritte..].windows(2) {
+                    self.draw_linie(schritt[0], schritt[1], ORANGE);
+                }
+            }
+            let (x, y) = self.layout.get_mitte(zug.pfad[schritte + 1]);
+            let radius = self.layout.feld_size as f64 * 0.15;
+            self.leinwand.draw_kreis(x, y, radius, GREEN, 0.85);
+        }
+    }
+
+    fn draw_hinweis(&mut self) {
+        let zug = match &self.hinweis {
+            Some((brett, zug)) if *brett == self.brett => zug.clone(),
+            _ => return,
+        };
+        for position in [zug.von(), zug.nach()] {
+            let (x, y) = self.layout.get_mitte(position);
+            let radius = self.layout.feld_size as f64 * 0.1;
+            self.leinwand.draw_kreis(x, y, radius, YELLOW, 1.0);
+        }
+    }
+
+    fn draw_analyse(&mut self) {
+        let analysen = match &self.analyse {
+            Some((brett, analysen)) if *brett == self.brett && !analysen.is_empty() => {
+                analysen.clone()
+            }
+            _ => return,
+        };
+
+        // Gewinne und Verluste aus der Endspieldatenbank sollen die Skala nicht verzerren
+        let bewertung = |analyse: &ZugAnalyse| {
+            analyse
+                .bewertung
+                .clamp(-Wert::GEWINN_BEWERTUNG, Wert::GEWINN_BEWERTUNG) as f64
+        };
+        let max = analysen.iter().map(bewertung).fold(f64::MIN, f64::max);
+        let min = analysen.iter().map(bewertung).fold(f64::MAX, f64::min);
+
+        // Die Züge sind absteigend sortiert, also gewinnt bei gleichem Zielfeld der beste Zug
+        for analyse in analysen.iter().rev() {
+            let anteil = if max > min {
+                (bewertung(analyse) - min) / (max - min)
+            } else {
+                1.0
+            };
+            let color = rgb((255.0 * (1.0 - anteil)) as u8, (255.0 * anteil) as u8, 0);
+            let (x, y) = self.layout.get_mitte(analyse.zug.nach());
+            let radius = self.layout.feld_size as f64 * 0.2;
+            self.leinwand.draw_kreis(x, y, radius, color, 1.0);
+        }
+    }
+
+    /// Bringt die Partie auf den Stand des Bretts, sodass `am_zug` ziehen kann. Ist das Brett
+    /// durch einen erlaubten Zug entstanden, wird er in der Partie nachgezogen, sonst beginnt
+    /// eine neue Partie.
+    fn update_partie(&mut self, am_zug: Spieler) {
+        if *self.partie.get_brett() != self.brett || self.partie.get_am_zug() != am_zug {
+            let mut partie = self.partie.clone();
+            if partie.get_am_zug() == am_zug || partie.ziehe(self.brett.clone()).is_none() {
+                partie = Partie::new(self.brett.clone(), am_zug, Remisregeln::default());
+                self.zuege.clear();
+            }
+            self.partie = partie;
+        }
+    }
+
+    /// Führt einen Zug von `am_zug` aus und hält Partie und Zugliste auf dem Stand. Ein eigener
+    /// Zug in einer Netzpartie wird nur ausgeführt, wenn der Gegner ihn bekommen hat.
+    fn ziehe(&mut self, am_zug: Spieler, nachher: SpielBrett) {
+        // Die Bedenkzeit bis hierher gehört noch zu diesem Zug
+        self.update_zeit();
+        if self.get_steuerung(am_zug) != Steuerung::Netzwerk {
+            if let Some(netz) = &mut self.netz {
+                // Mit einem Zug ist ein offenes Remisangebot abgelehnt
+                if std::mem::take(&mut self.remis_angebot) {
+                    netz.beantworte_remis(false);
+                }
+                if netz.sende_zug(&nachher).is_none() {
+                    self.melde("Der Zug konnte nicht gesendet werden");
+                    self.auswahl.clear();
+                    return;
+                }
+            }
+        }
+        if let Some(uhr) = &mut self.uhr {
+            uhr.zug_beendet(am_zug);
+        }
+        self.update_partie(am_zug);
+        if let Some(zug) = self.brett.get_zug(&nachher, am_zug) {
+            self.zuege.push((am_zug, zug.to_notation()));
+            let dame = matches!(self.brett.get(zug.von()), Feld::Stein(_))
+                && matches!(nachher.get(zug.nach()), Feld::Dame(_));
+            self.spiele_geraeusch(if dame {
+                Geraeusch::Dame
+            } else if zug.is_schlagen() {
+                Geraeusch::Schlagen
+            } else {
+                Geraeusch::Zug
+            });
+            self.animation =
+                self.animations_tempo
+                    .get_dauer_pro_schritt()
+                    .map(|dauer_pro_schritt| Animation {
+                        vorher: self.brett.clone(),
+                        zug: zug.clone(),
+                        dauer_pro_schritt,
+                        verstrichen: Duration::ZERO,
+                    });
+            self.letzter_zug = Some((nachher.clone(), zug));
+        }
+        self.partie.ziehe(nachher.clone());
+        self.brett = nachher;
+        self.auswahl.clear();
+        self.spiele_spielende();
+    }
+
+    fn spiele_engine_zug(&mut self, am_zug: Spieler, depth: u32) {
+        self.update_partie(am_zug);
+        let spielstand = self.get_spielstand();
+        if spielstand != Spielstand::Laeuft {
+            return;
+        }
+
+        let mut einstellungen = SuchEinstellungen {
+            gewichte: self.gewichte,
+            ..SuchEinstellungen::new(depth)
+        };
+        if let Some(uhr) = &self.uhr {
+            einstellungen.max_depth = UHR_DEPTH;
+            einstellungen.max_zeit = Some(uhr.get_zeit_fuer_zug(am_zug));
+        }
+        let ergebnis = self.partie.get_best_move_mit_einstellungen(
+            &einstellungen,
+            Some(&self.buch),
+            self.datenbank.as_ref(),
+            Some(&mut self.zufall),
+        );
+        self.window
+            .set_title(&format!("Dame - Bewertung {}", ergebnis.bewertung));
+        self.bewertung = Some((am_zug, ergebnis.bewertung, ergebnis.depth));
+        self.ziehe(am_zug, ergebnis.best_move);
+        let spielstand = self.get_spielstand();
+        if spielstand != Spielstand::Laeuft {
+            self.window.set_title(&format!("Dame - {spielstand:?}"));
+        }
+    }
+
     fn handle_input(&mut self) {
-        if self.window.is_key_released(Key::Space) {
-            self.brett = self.brett.get_best_move(7);
+        if self.window.is_key_released(Key::T) {
+            self.animations_tempo = self.animations_tempo.next();
+            self.melde(format!("Animation: {:?}", self.animations_tempo));
+        }
+        if self.window.is_key_released(Key::V) {
+            self.zeige_balken = !self.zeige_balken;
+        }
+        if self.window.is_key_released(Key::L) {
+            self.ton_an = !self.ton_an;
+            if !self.lautsprecher.is_verfuegbar() {
+                self.melde("Ton: kein Audiogerät");
+            } else {
+                self.melde(format!("Ton: {}", if self.ton_an { "an" } else { "aus" }));
+            }
+        }
+        // Erst weiterspielen, wenn der letzte Zug zu sehen war
+        if self.animation.is_some() {
+            return;
         }
 
-        if let Some((mouse_x, mouse_y)) = self.window.get_mouse_pos(MouseMode::Discard) {
-            let spalte = mouse_x as usize / FELD_SIZE;
-            let zeile = mouse_y as usize / FELD_SIZE;
-            let position = Position { spalte, zeile };
-            if !position.valid() {
+        if self.window.is_key_released(Key::D) {
+            self.layout.gedreht = !self.layout.gedreht;
+        }
+        // In einer Netzpartie sind Farben und Bedenkzeit beim Verbinden festgelegt
+        if self.netz.is_some() {
+            self.update_netz();
+            self.handle_netz_input();
+            // Ohne Gegner wird nicht gezogen, auch nicht nach einer Trennung
+            if self.netz.as_ref().is_some_and(|netz| !netz.is_verbunden()) {
                 return;
             }
-            if self.window.is_key_released(Key::Backspace) {
-                self.brett.set(position, Feld::Leer);
-            } else if self.window.is_key_released(Key::M) {
-                if self.window.is_key_down(Key::LeftShift) {
-                    self.brett.set(position, Feld::Dame(Spieler::Mensch));
-                } else {
-                    self.brett.set(position, Feld::Stein(Spieler::Mensch));
+        } else {
+            if self.window.is_key_released(Key::F) {
+                self.mensch_seite = !self.mensch_seite;
+                if self.steuerung_schwarz != self.steuerung_weiss {
+                    self.spiele_gegen_engine();
                 }
-            } else if self.window.is_key_released(Key::C) {
-                if self.window.is_key_down(Key::LeftShift) {
-                    self.brett.set(position, Feld::Dame(Spieler::Computer));
-                } else {
-                    self.brett.set(position, Feld::Stein(Spieler::Computer));
+            }
+            if self.window.is_key_released(Key::B) {
+                self.beginner = !self.beginner;
+            }
+            if self.window.is_key_released(Key::Z) {
+                let index = ZEITKONTROLLEN
+                    .iter()
+                    .position(|&kontrolle| kontrolle == self.zeitkontrolle)
+                    .unwrap_or(0);
+                self.zeitkontrolle = ZEITKONTROLLEN[(index + 1) % ZEITKONTROLLEN.len()];
+                self.uhr = self.zeitkontrolle.map(Uhr::new);
+            }
+            if self.window.is_key_released(Key::S) {
+                match self.editor {
+                    Some(_) => self.beende_editor(false),
+                    None => self.starte_editor(),
+                }
+                return;
+            }
+            if self.editor.is_some() {
+                self.handle_editor_input();
+                return;
+            }
+            if self.window.is_key_released(Key::N) {
+                self.neues_spiel();
+                return;
+            }
+            if self.window.is_key_released(Key::Key2) {
+                match (self.steuerung_schwarz, self.steuerung_weiss) {
+                    (Steuerung::Mensch, Steuerung::Mensch) => self.spiele_gegen_engine(),
+                    _ => self.set_steuerung(Steuerung::Mensch, Steuerung::Mensch),
+                }
+            }
+            if self.window.is_key_released(Key::E) {
+                match (self.steuerung_schwarz, self.steuerung_weiss) {
+                    (Steuerung::Engine, Steuerung::Engine) => self.spiele_gegen_engine(),
+                    _ => self.set_steuerung(Steuerung::Engine, Steuerung::Engine),
                 }
             }
         }
+        // Nach Ablauf der Zeit, Aufgabe oder vereinbartem Remis wird nicht weitergespielt
+        if self.get_zeitverlust().is_some() || self.ende.is_some() {
+            return;
+        }
+
+        let am_zug = self.get_aktuelle_partie().get_am_zug();
+        if self.get_steuerung(am_zug) == Steuerung::Engine
+            && self.get_aktuelle_partie().get_spielstand() == Spielstand::Laeuft
+        {
+            // Ohne Mensch am Brett wird flacher gesucht, damit die Partie zügig läuft
+            let depth = match self.get_steuerung(!am_zug) {
+                Steuerung::Engine => SELBSTSPIEL_DEPTH,
+                Steuerung::Mensch | Steuerung::Netzwerk => SUCH_DEPTH,
+            };
+            self.spiele_engine_zug(am_zug, depth);
+            return;
+        }
+
+        if self.window.is_key_released(Key::H) {
+            self.update_partie(am_zug);
+            let einstellungen = SuchEinstellungen {
+                gewichte: self.gewichte,
+                ..SuchEinstellungen::new(SUCH_DEPTH)
+            };
+            let ergebnis = self.partie.get_best_move_mit_einstellungen(
+                &einstellungen,
+                Some(&self.buch),
+                self.datenbank.as_ref(),
+                None,
+            );
+            self.hinweis = self
+                .brett
+                .get_zug(&ergebnis.best_move, am_zug)
+                .map(|zug| (self.brett.clone(), zug));
+            if let Some(notation) = self.hinweis.as_ref().map(|(_, zug)| zug.to_notation()) {
+                self.melde(format!("Hinweis: {notation}"));
+            }
+        }
+
+        if self.window.is_key_released(Key::A) {
+            self.analysiere(self.get_aktuelle_partie().get_am_zug());
+        }
+
+        if self.window.is_key_released(Key::Space) && self.netz.is_none() {
+            self.spiele_engine_zug(am_zug, SUCH_DEPTH);
+            return;
+        }
+
+        let maus_gedrueckt = self.window.get_mouse_down(MouseButton::Left);
+        let geklickt = maus_gedrueckt && !self.maus_gedrueckt;
+        let losgelassen = !maus_gedrueckt && self.maus_gedrueckt;
+        self.maus_gedrueckt = maus_gedrueckt;
+
+        let maus = self.window.get_mouse_pos(MouseMode::Clamp);
+        let position = maus.and_then(|(x, y)| self.layout.get_position(x, y));
+        if self.gezogen.is_some() {
+            if losgelassen {
+                self.lasse_los(position);
+            } else {
+                self.gezogen = maus;
+            }
+        }
+        if let (Some(position), true) = (position, geklickt) {
+            self.klicke(position);
+            // Die Figur bleibt an der Maus, bis sie losgelassen wird
+            if self.auswahl.last() == Some(&position) {
+                self.gezogen = maus;
+            }
+        }
+    }
+
+    /// Zeigt `text` in der Seitenleiste, bis die nächste Meldung kommt
+    fn melde(&mut self, text: impl Into<String>) {
+        self.meldung = Some(text.into());
+    }
+
+    /// Wartet im Netzwerk auf einen Gegner, der die andere Seite spielt
+    fn hoste(&mut self, port: u16) -> io::Result<()> {
+        let mut angebot = Spielangebot::new(!self.mensch_seite);
+        angebot.zeitkontrolle = self.zeitkontrolle;
+        angebot.start_am_zug = self.beginner;
+        self.netz = Some(Netzpartie::hoste(("0.0.0.0", port), angebot)?);
+        self.melde(format!("Warte auf Gegner an Port {port}"));
+        self.neues_spiel();
+        self.spiele_gegen_netz(self.mensch_seite);
+        self.layout.gedreht = self.mensch_seite == Spieler::Schwarz;
+        Ok(())
+    }
+
+    fn verbinde(&mut self, adresse: &str) -> io::Result<()> {
+        self.netz = Some(Netzpartie::verbinde(adresse)?);
+        self.melde(format!("Verbunden mit {adresse}"));
+        Ok(())
+    }
+
+    /// Verarbeitet, was vom Gegner im Netzwerk angekommen ist
+    fn update_netz(&mut self) {
+        let Some(netz) = &mut self.netz else {
+            return;
+        };
+        for ereignis in netz.poll() {
+            match ereignis {
+                Ereignis::Verbunden(partie) => self.setze_netzpartie(partie),
+                Ereignis::Zug(nachher) => {
+                    self.remis_angebot = false;
+                    self.ziehe(!self.mensch_seite, nachher);
+                }
+                Ereignis::Aufgabe => {
+                    self.melde("Der Gegner gibt auf");
+                    self.ende = Some(Spielstand::Gewonnen(self.mensch_seite));
+                    self.spiele_spielende();
+                }
+                Ereignis::Zeitverlust => {
+                    self.melde("Die Zeit des Gegners ist abgelaufen");
+                    self.ende = Some(Spielstand::Gewonnen(self.mensch_seite));
+                    self.spiele_spielende();
+                }
+                Ereignis::RemisAngebot => {
+                    self.melde("Der Gegner bietet Remis an");
+                    self.remis_angebot = true;
+                }
+                Ereignis::Remis => {
+                    self.melde("Remis angenommen");
+                    self.ende = Some(Spielstand::Remis(RemisGrund::Vereinbart));
+                }
+                Ereignis::RemisAbgelehnt => self.melde("Remis abgelehnt"),
+                Ereignis::Getrennt => self.melde("Verbindung getrennt"),
+                Ereignis::Fehler(text) => self.melde(format!("Netzwerk: {text}")),
+            }
+        }
+    }
+
+    /// Spielt die eigene Farbe der Netzpartie und übernimmt den Stand des Gastgebers, wenn er
+    /// nicht schon auf dem Brett steht
+    fn setze_netzpartie(&mut self, partie: Partie) {
+        let Some(netz) = &self.netz else {
+            return;
+        };
+        let (Some(farbe), Some(angebot)) = (netz.get_eigene_farbe(), netz.get_angebot().cloned())
+        else {
+            return;
+        };
+        let zuege = netz.get_zuege().to_vec();
+        self.melde(format!(
+            "Gegner verbunden, du spielst {}",
+            self.get_name(farbe)
+        ));
+        self.spiele_gegen_netz(farbe);
+        // Nach dem Wiederverbinden geht es mit derselben Uhr weiter
+        if self.partie == partie
+            && self.brett == *partie.get_brett()
+            && self.zeitkontrolle == angebot.zeitkontrolle
+        {
+            return;
+        }
+
+        let mut am_zug = angebot.start_am_zug;
+        self.zuege = zuege
+            .into_iter()
+            .map(|notation| {
+                let eintrag = (am_zug, notation);
+                am_zug = !am_zug;
+                eintrag
+            })
+            .collect();
+        self.zeitkontrolle = angebot.zeitkontrolle;
+        self.uhr = self.zeitkontrolle.map(Uhr::new);
+        self.brett = partie.get_brett().clone();
+        self.partie = partie;
+        self.ende = None;
+        self.remis_angebot = false;
+        self.bewertung = None;
+        self.letzter_zug = None;
+        self.auswahl.clear();
+        self.layout.gedreht = farbe == Spieler::Schwarz;
+    }
+
+    fn handle_netz_input(&mut self) {
+        let Some(netz) = &mut self.netz else {
+            return;
+        };
+        if self.ende.is_some() || !netz.is_verbunden() {
+            return;
+        }
+        if self.window.is_key_released(Key::R) {
+            if !self.remis_angebot {
+                netz.biete_remis_an();
+                self.meldung = Some("Remis angeboten".to_string());
+            } else if netz.beantworte_remis(true).is_some() {
+                self.ende = Some(Spielstand::Remis(RemisGrund::Vereinbart));
+            }
+            self.remis_angebot = false;
+        }
+        if self.window.is_key_released(Key::K) && std::mem::take(&mut self.remis_angebot) {
+            netz.beantworte_remis(false);
+        }
+        if self.window.is_key_released(Key::G) && netz.gib_auf().is_some() {
+            self.melde("Du gibst auf");
+            self.ende = Some(Spielstand::Gewonnen(!self.mensch_seite));
+            self.spiele_spielende();
+        }
+    }
+
+    fn analysiere(&mut self, am_zug: Spieler) {
+        let analysen = self.brett.analysiere(
+            am_zug,
+            ANALYSE_DEPTH,
+            self.datenbank.as_ref(),
+            &self.gewichte,
+        );
+        // Die Züge sind absteigend sortiert, alle stehen farbig auf dem Brett
+        if let Some(analyse) = analysen.first() {
+            self.melde(format!(
+                "Bester Zug {}: {:+}",
+                analyse.zug.to_notation(),
+                analyse.bewertung
+            ));
+        }
+        self.analyse = Some((self.brett.clone(), analysen));
+    }
+
+    fn starte_editor(&mut self) {
+        self.editor = Some(Editor {
+            figur: Feld::Stein(Spieler::Schwarz),
+            am_zug: self.get_aktuelle_partie().get_am_zug(),
+        });
+        self.auswahl.clear();
+        self.gezogen = None;
+    }
+
+    /// Beginnt eine neue Partie aus der aufgebauten Stellung
+    fn beende_editor(&mut self, analysieren: bool) {
+        let Some(editor) = self.editor.take() else {
+            return;
+        };
+        let fehler = self.brett.pruefe_aufstellung(editor.am_zug);
+        if !fehler.is_empty() {
+            let fehler: Vec<String> = fehler.iter().map(|fehler| fehler.to_string()).collect();
+            self.melde(format!("Warnung: {}", fehler.join(", ")));
+        }
+        self.partie = Partie::new(self.brett.clone(), editor.am_zug, Remisregeln::default());
+        self.uhr = self.zeitkontrolle.map(Uhr::new);
+        self.zuege.clear();
+        self.bewertung = None;
+        self.letzter_zug = None;
+        if analysieren {
+            self.analysiere(editor.am_zug);
+        }
+    }
+
+    fn fuehre_aus(&mut self, aktion: EditorAktion) {
+        match aktion {
+            EditorAktion::Figur(figur) => {
+                if let Some(editor) = &mut self.editor {
+                    editor.figur = figur;
+                }
+            }
+            EditorAktion::Leeren => self.brett = SpielBrett::default(),
+            EditorAktion::Startaufstellung => self.brett = SpielBrett::startaufstellung(),
+            EditorAktion::AmZug => {
+                if let Some(editor) = &mut self.editor {
+                    editor.am_zug = !editor.am_zug;
+                }
+            }
+            EditorAktion::Spielen => self.beende_editor(false),
+            EditorAktion::Analysieren => self.beende_editor(true),
+        }
+    }
+
+    fn handle_editor_input(&mut self) {
+        let Some(editor) = self.editor else {
+            return;
+        };
+        let maus_gedrueckt = self.window.get_mouse_down(MouseButton::Left);
+        let geklickt = maus_gedrueckt && !self.maus_gedrueckt;
+        self.maus_gedrueckt = maus_gedrueckt;
+
+        let Some((x, y)) = self.window.get_mouse_pos(MouseMode::Discard) else {
+            return;
+        };
+        if geklickt {
+            let knopf = self
+                .get_editor_knoepfe()
+                .into_iter()
+                .find(|knopf| knopf.enthaelt(x, y));
+            if let Some(knopf) = knopf {
+                self.fuehre_aus(knopf.aktion);
+                return;
+            }
+        }
+
+        let Some(position) = self.layout.get_position(x, y) else {
+            return;
+        };
+        let dame = self.window.is_key_down(Key::LeftShift);
+        let feld = if maus_gedrueckt {
+            Some(editor.figur)
+        } else if self.window.get_mouse_down(MouseButton::Right)
+            || self.window.is_key_released(Key::Backspace)
+        {
+            Some(Feld::Leer)
+        } else if self.window.is_key_released(Key::M) {
+            Some(if dame {
+                Feld::Dame(Spieler::Schwarz)
+            } else {
+                Feld::Stein(Spieler::Schwarz)
+            })
+        } else if self.window.is_key_released(Key::C) {
+            Some(if dame {
+                Feld::Dame(Spieler::Weiss)
+            } else {
+                Feld::Stein(Spieler::Weiss)
+            })
+        } else {
+            None
+        };
+        if let Some(feld) = feld {
+            self.brett.set(position, feld);
+        }
+    }
+
+    /// Beginnt ein neues Spiel aus der Startaufstellung, in dem `beginner` zuerst zieht
+    fn neues_spiel(&mut self) {
+        self.brett = SpielBrett::startaufstellung();
+        self.partie = Partie::new(self.brett.clone(), self.beginner, Remisregeln::default());
+        self.uhr = self.zeitkontrolle.map(Uhr::new);
+        self.zuege.clear();
+        self.bewertung = None;
+        self.ende = None;
+        self.auswahl.clear();
+        self.window.set_title("Dame");
+    }
+
+    /// Zieht die ausgewählte Figur einen Schritt weiter auf das Feld. Ist der Zug damit
+    /// vollständig, wird er ausgeführt. Gibt zurück, ob der Schritt erlaubt war.
+    fn setze_schritt(&mut self, position: Position) -> bool {
+        let Some(am_zug) = self.get_maus_spieler() else {
+            return false;
+        };
+        let mut pfad = self.auswahl.clone();
+        pfad.push(position);
+        let zuege: Vec<_> = self
+            .get_auswahl_zuege()
+            .into_iter()
+            .filter(|(zug, _)| zug.pfad.starts_with(&pfad))
+            .collect();
+        if let Some((_, nachher)) = zuege.iter().find(|(zug, _)| zug.pfad.len() == pfad.len()) {
+            self.ziehe(am_zug, nachher.clone());
+            return true;
+        }
+        if zuege.is_empty() {
+            return false;
+        }
+        self.auswahl = pfad;
+        true
+    }
+
+    /// Wählt einen Stein des Menschen am Zug aus oder zieht den ausgewählten Stein auf das Feld
+    fn klicke(&mut self, position: Position) {
+        let Some(am_zug) = self.get_maus_spieler() else {
+            return;
+        };
+        if self.auswahl.last() == Some(&position) || self.setze_schritt(position) {
+            return;
+        }
+        // Ein Klick auf das Zielfeld führt auch einen Mehrfachschlag ganz aus
+        let zug = self
+            .get_auswahl_zuege()
+            .into_iter()
+            .find(|(zug, _)| zug.nach() == position);
+        if let Some((_, nachher)) = zug {
+            self.ziehe(am_zug, nachher);
+            return;
+        }
+
+        self.auswahl = if self.brett.get(position).is_spieler(am_zug) {
+            vec![position]
+        } else {
+            Vec::new()
+        };
+    }
+
+    /// Lässt die gezogene Figur auf dem Feld los. Ist der Schritt nicht erlaubt, springt sie
+    /// zurück.
+    fn lasse_los(&mut self, position: Option<Position>) {
+        self.gezogen = None;
+        let Some(position) = position else {
+            return;
+        };
+        if self.auswahl.last() != Some(&position)
+            && self.setze_schritt(position)
+            && self.auswahl.is_empty()
+        {
+            // Die Figur wurde schon an ihr Ziel gezogen
+            self.animation = None;
+        }
     }
 
     fn update_loop(mut self) {
         while self.window.is_open() && !self.window.is_key_down(Key::Escape) {
+            self.update_zeit();
+            self.update_size();
             self.handle_input();
+            self.update_balken();
 
             self.draw();
 
             self.window
-                .update_with_buffer(&self.buffer, SIZE, SIZE)
+                .update_with_buffer(
+                    self.leinwand.get_buffer(),
+                    self.leinwand.get_breite(),
+                    self.leinwand.get_hoehe(),
+                )
                 .unwrap();
         }
     }
 }
 
+/// Wie die Partie über die Kommandozeile gestartet wird
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+struct Argumente {
+    host: Option<u16>,
+    verbinde: Option<String>,
+    zeitkontrolle: Option<Zeitkontrolle>,
+    weiss: bool,
+    hub: bool,
+}
+
+fn parse_argumente(argumente: &[String]) -> Option<Argumente> {
+    let mut result = Argumente::default();
+    let mut argumente = argumente.iter().peekable();
+    while let Some(argument) = argumente.next() {
+        match argument.as_str() {
+            "--host" => {
+                result.host = Some(match argumente.next_if(|wert| !wert.starts_with("--")) {
+                    Some(port) => port.parse().ok()?,
+                    None => STANDARD_PORT,
+                })
+            }
+            "--verbinde" => result.verbinde = Some(argumente.next()?.clone()),
+            "--zeit" => result.zeitkontrolle = Some(Zeitkontrolle::parse(argumente.next()?)?),
+            "--weiss" => result.weiss = true,
+            "--hub" => result.hub = true,
+            _ => return None,
+        }
+    }
+    // Die Engine für das Hub-Protokoll hat keine weiteren Einstellungen
+    let nur_hub = Argumente {
+        hub: true,
+        ..Argumente::default()
+    };
+    let gueltig = match result.hub {
+        true => result == nur_hub,
+        false => result.host.is_none() || result.verbinde.is_none(),
+    };
+    gueltig.then_some(result)
+}
+
 fn main() {
-    match Application::open() {
-        Some(app) => app.update_loop(),
-        None => {}
+    let argumente: Vec<String> = env::args().skip(1).collect();
+    let Some(argumente) = parse_argumente(&argumente) else {
+        eprintln!("{VERWENDUNG}");
+        return;
+    };
+    if argumente.hub {
+        let mut engine = Engine::new(EngineOptionen {
+            gewichte: Gewichte::load(GEWICHTE_DATEI).unwrap_or_default(),
+            ..EngineOptionen::default()
+        });
+        engine.set_eroeffnungsbuch(Eroeffnungsbuch::load(EROEFFNUNGSBUCH_DATEI).map(Arc::new));
+        engine
+            .set_endspieldatenbank(Endspieldatenbank::load(ENDSPIELDATENBANK_DATEI).map(Arc::new));
+        if let Err(fehler) = hub::run(Hub::new(engine)) {
+            eprintln!("Hub: {fehler}");
+        }
+        return;
+    }
+    let Some(mut app) = Application::open() else {
+        return;
+    };
+    if argumente.weiss {
+        app.mensch_seite = Spieler::Weiss;
+        app.spiele_gegen_engine();
+    }
+    app.zeitkontrolle = argumente.zeitkontrolle;
+    app.uhr = app.zeitkontrolle.map(Uhr::new);
+    let netz = match (argumente.host, &argumente.verbinde) {
+        (Some(port), _) => app.hoste(port),
+        (_, Some(adresse)) => app.verbinde(adresse),
+        (None, None) => Ok(()),
+    };
+    // Ohne Netzwerk geht es mit einer Partie am eigenen Brett weiter
+    if let Err(fehler) = netz {
+        app.melde(format!("Netzwerk: {fehler}"));
+    }
+    app.update_loop();
+}
+
+/// Die Bewertung der Stellung aus Sicht von Schwarz für den Bewertungsbalken
+fn get_balken_bewertung(
+    partie: &Partie,
+    gewichte: Gewichte,
+    datenbank: Option<&Endspieldatenbank>,
+) -> i32 {
+    match partie.get_spielstand() {
+        Spielstand::Laeuft => {
+            let einstellungen = SuchEinstellungen {
+                gewichte,
+                ..SuchEinstellungen::new(ANALYSE_DEPTH)
+            };
+            let ergebnis =
+                partie.get_best_move_mit_einstellungen(&einstellungen, None, datenbank, None);
+            match partie.get_am_zug() {
+                Spieler::Schwarz => ergebnis.bewertung,
+                Spieler::Weiss => ergebnis.bewertung.saturating_neg(),
+            }
+        }
+        Spielstand::Gewonnen(Spieler::Schwarz) => Wert::GEWINN_BEWERTUNG,
+        Spielstand::Gewonnen(Spieler::Weiss) => -Wert::GEWINN_BEWERTUNG,
+        Spielstand::Remis(_) => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_argumente() {
+        let argumente = |text: &str| {
+            parse_argumente(
+                &text
+                    .split_whitespace()
+                    .map(str::to_string)
+                    .collect::<Vec<_>>(),
+            )
+        };
+        assert_eq!(
+            argumente("--host --zeit 300+3"),
+            Some(Argumente {
+                host: Some(STANDARD_PORT),
+                verbinde: None,
+                zeitkontrolle: Zeitkontrolle::parse("300+3"),
+                weiss: false,
+                hub: false,
+            })
+        );
+        assert_eq!(argumente("--host 9000").unwrap().host, Some(9000));
+        assert_eq!(
+            argumente("--verbinde 192.168.1.2")
+                .unwrap()
+                .verbinde
+                .as_deref(),
+            Some("192.168.1.2")
+        );
+        assert_eq!(argumente("--host --verbinde localhost"), None);
+        assert!(argumente("--hub").unwrap().hub);
+        assert_eq!(argumente("--hub --weiss"), None);
+        assert_eq!(argumente("--zeit 5"), None);
+        assert_eq!(argumente("--unbekannt"), None);
+    }
+
+    #[test]
+    fn test_layout() {
+        let layout = Layout::new(1200, 400, false);
+        assert_eq!(layout.feld_size, 50);
+        assert_eq!(layout.panel_x, 1200 - PANEL_BREITE);
+        // Das Brett ist im freien Bereich links von der Seitenleiste zentriert
+        assert_eq!(layout.brett_x, (1200 - PANEL_BREITE - 400) / 2);
+        assert_eq!(layout.brett_y, 0);
+
+        let position = Position {
+            spalte: 3,
+            zeile: 1,
+        };
+        let (x, y) = layout.get_mitte(position);
+        assert_eq!(layout.get_position(x as f32, y as f32), Some(position));
+        assert_eq!(layout.get_position(0.0, 0.0), None);
+        assert_eq!(layout.get_position(layout.panel_x as f32, 10.0), None);
+
+        // Gedreht liegt das Feld oben links unten rechts
+        let gedreht = Layout::new(1200, 400, true);
+        let ecke = Position {
+            spalte: 0,
+            zeile: 0,
+        };
+        assert_eq!(
+            gedreht.get_ecke(ecke),
+            (gedreht.brett_x + 350, gedreht.brett_y + 350)
+        );
+        let (x, y) = gedreht.get_mitte(position);
+        assert_eq!(gedreht.get_position(x as f32, y as f32), Some(position));
+    }
+
+    #[test]
+    fn test_balken_bewertung() {
+        // Weiss muss auf ein Feld ziehen, auf dem die Dame den letzten Stein schlägt
+        let brett = SpielBrett::parse(concat!(
+            "_ _ _ _ \n",
+            " _ _ _ _\n",
+            "_ M _ _ \n",
+            " _ _ _ _\n",
+            "c _ _ _ \n",
+            " _ _ _ _\n",
+            "_ _ _ _ \n",
+            " _ _ _ _",
+        ))
+        .unwrap();
+        let partie = Partie::new(brett, Spieler::Weiss, Remisregeln::default());
+        assert_eq!(
+            get_balken_bewertung(&partie, Gewichte::default(), None),
+            i32::MAX
+        );
+    }
+
+    #[test]
+    fn test_animation() {
+        let brett = SpielBrett::startaufstellung();
+        let (zug, _) = brett.get_possible_zuege(Spieler::Schwarz).remove(0);
+        let mut animation = Animation {
+            vorher: brett,
+            zug,
+            dauer_pro_schritt: AnimationsTempo::Normal.get_dauer_pro_schritt().unwrap(),
+            verstrichen: Duration::ZERO,
+        };
+        assert_eq!(animation.get_fortschritt(), (0, 0.0));
+        animation.verstrichen = animation.dauer_pro_schritt / 2;
+        assert_eq!(animation.get_fortschritt(), (0, 0.5));
+        assert!(!animation.is_fertig());
+        animation.verstrichen = animation.dauer_pro_schritt * 2;
+        assert_eq!(animation.get_fortschritt(), (0, 1.0));
+        assert!(animation.is_fertig());
+        assert_eq!(AnimationsTempo::Aus.get_dauer_pro_schritt(), None);
     }
 }
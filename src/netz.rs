@@ -0,0 +1,840 @@
+use std::{
+    io::{self, ErrorKind, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    brett::{Partie, Remisregeln, SpielBrett, Spieler},
+    pdn::{parse_fen, to_fen},
+    uhr::Zeitkontrolle,
+};
+
+pub const STANDARD_PORT: u16 = 7654;
+/// Wird erhöht, wenn sich Nachrichten inkompatibel ändern
+pub const PROTOKOLL_VERSION: u32 = 2;
+/// Die einzige Regelvariante, die beide Seiten spielen können
+pub const VARIANTE: &str = "dame";
+const VERBINDEN_TIMEOUT: Duration = Duration::from_secs(1);
+/// So lange wartet der Gast nach einem fehlgeschlagenen Versuch, bevor er neu verbindet
+const WIEDERVERBINDEN_NACH: Duration = Duration::from_secs(2);
+
+/// Die Bedingungen der Partie, die der Gastgeber beim Verbinden festlegt
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Spielangebot {
+    pub gast_farbe: Spieler,
+    pub regeln: Remisregeln,
+    pub zeitkontrolle: Option<Zeitkontrolle>,
+    pub start: SpielBrett,
+    pub start_am_zug: Spieler,
+}
+
+impl Spielangebot {
+    pub fn new(gast_farbe: Spieler) -> Self {
+        Self {
+            gast_farbe,
+            regeln: Remisregeln::default(),
+            zeitkontrolle: None,
+            start: SpielBrett::startaufstellung(),
+            start_am_zug: Spieler::Schwarz,
+        }
+    }
+}
+
+fn farbe_to_text(spieler: Spieler) -> &'static str {
+    match spieler {
+        Spieler::Schwarz => "schwarz",
+        Spieler::Weiss => "weiss",
+    }
+}
+
+fn text_to_farbe(text: &str) -> Option<Spieler> {
+    match text {
+        "schwarz" => Some(Spieler::Schwarz),
+        "weiss" => Some(Spieler::Weiss),
+        _ => None,
+    }
+}
+
+/// Eine Zeile des Protokolls. Der Gast meldet sich mit `HALLO`, der Gastgeber antwortet mit
+/// `SPIEL` und den bisherigen Zügen in `VERLAUF`, danach werden Züge mit ihrer Nummer
+/// ausgetauscht. Vorher sind keine anderen Nachrichten erlaubt.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Nachricht {
+    Hallo {
+        version: u32,
+    },
+    Spiel(Spielangebot),
+    Verlauf(Vec<String>),
+    /// Der Zug mit der Nummer `nummer`, von 0 an gezählt ab der Startstellung
+    Zug {
+        nummer: usize,
+        notation: String,
+    },
+    Aufgabe,
+    /// Die Zeit des Absenders ist abgelaufen, er hat verloren
+    Zeitverlust,
+    RemisAngebot,
+    RemisAnnahme,
+    RemisAblehnung,
+    Fehler(String),
+}
+
+impl Nachricht {
+    pub fn to_zeile(&self) -> String {
+        match self {
+            Self::Hallo { version } => format!("HALLO {version}"),
+            Self::Spiel(angebot) => format!(
+                "SPIEL variante={VARIANTE} gast={} wiederholungen={} ohne_fortschritt={} \
+damen_endspiel={} zeit={} start={}",
+                farbe_to_text(angebot.gast_farbe),
+                angebot.regeln.wiederholungen,
+                match angebot.regeln.max_zuege_ohne_fortschritt {
+                    Some(zuege) => zuege.to_string(),
+                    None => "-".to_string(),
+                },
+                u8::from(angebot.regeln.damen_endspiel),
+                match angebot.zeitkontrolle {
+                    Some(zeitkontrolle) => zeitkontrolle.to_kurzform(),
+                    None => "-".to_string(),
+                },
+                to_fen(&angebot.start, angebot.start_am_zug),
+            ),
+            Self::Verlauf(zuege) if zuege.is_empty() => "VERLAUF".to_string(),
+            Self::Verlauf(zuege) => format!("VERLAUF {}", zuege.join(" ")),
+            Self::Zug { nummer, notation } => format!("ZUG {nummer} {notation}"),
+            Self::Aufgabe => "AUFGABE".to_string(),
+            Self::Zeitverlust => "ZEITVERLUST".to_string(),
+            Self::RemisAngebot => "REMIS_ANGEBOT".to_string(),
+            Self::RemisAnnahme => "REMIS_ANNAHME".to_string(),
+            Self::RemisAblehnung => "REMIS_ABLEHNUNG".to_string(),
+            Self::Fehler(text) => format!("FEHLER {text}"),
+        }
+    }
+
+    pub fn parse(zeile: &str) -> Option<Self> {
+        let zeile = zeile.trim();
+        let (befehl, rest) = zeile.split_once(' ').unwrap_or((zeile, ""));
+        Some(match befehl {
+            "HALLO" => Self::Hallo {
+                version: rest.trim().parse().ok()?,
+            },
+            "SPIEL" => Self::Spiel(parse_spielangebot(rest)?),
+            "VERLAUF" => Self::Verlauf(rest.split_whitespace().map(str::to_string).collect()),
+            "ZUG" => {
+                let (nummer, notation) = rest.trim().split_once(' ')?;
+                Self::Zug {
+                    nummer: nummer.parse().ok()?,
+                    notation: notation.trim().to_string(),
+                }
+            }
+            "AUFGABE" => Self::Aufgabe,
+            "ZEITVERLUST" => Self::Zeitverlust,
+            "REMIS_ANGEBOT" => Self::RemisAngebot,
+            "REMIS_ANNAHME" => Self::RemisAnnahme,
+            "REMIS_ABLEHNUNG" => Self::RemisAblehnung,
+            "FEHLER" => Self::Fehler(rest.trim().to_string()),
+            _ => return None,
+        })
+    }
+}
+
+/// Liest die Werte von `SPIEL`. Eine andere Variante als `VARIANTE` wird abgelehnt.
+fn parse_spielangebot(text: &str) -> Option<Spielangebot> {
+    let mut angebot = Spielangebot::new(Spieler::Weiss);
+    let mut variante = None;
+    for teil in text.split_whitespace() {
+        let (name, wert) = teil.split_once('=')?;
+        match name {
+            "variante" => variante = Some(wert),
+            "gast" => angebot.gast_farbe = text_to_farbe(wert)?,
+            "wiederholungen" => angebot.regeln.wiederholungen = wert.parse().ok()?,
+            "ohne_fortschritt" => {
+                angebot.regeln.max_zuege_ohne_fortschritt = match wert {
+                    "-" => None,
+                    wert => Some(wert.parse().ok()?),
+                }
+            }
+            "damen_endspiel" => angebot.regeln.damen_endspiel = wert == "1",
+            "zeit" => {
+                angebot.zeitkontrolle = match wert {
+                    "-" => None,
+                    wert => Some(Zeitkontrolle::parse(wert)?),
+                }
+            }
+            "start" => (angebot.start, angebot.start_am_zug) = parse_fen(wert)?,
+            // Unbekannte Werte von neueren Versionen werden übergangen
+            _ => (),
+        }
+    }
+    (variante? == VARIANTE).then_some(angebot)
+}
+
+/// Eine TCP-Verbindung, aus der gelesen wird, ohne zu blockieren
+struct Verbindung {
+    stream: TcpStream,
+    /// Empfangene Bytes nach der letzten vollständigen Zeile
+    eingang: Vec<u8>,
+}
+
+impl Verbindung {
+    fn new(stream: TcpStream) -> io::Result<Self> {
+        stream.set_nonblocking(true)?;
+        stream.set_nodelay(true)?;
+        Ok(Self {
+            stream,
+            eingang: Vec::new(),
+        })
+    }
+
+    fn sende(&mut self, nachricht: &Nachricht) -> io::Result<()> {
+        let zeile = nachricht.to_zeile() + "\n";
+        let mut daten = zeile.as_bytes();
+        while !daten.is_empty() {
+            match self.stream.write(daten) {
+                Ok(0) => return Err(ErrorKind::WriteZero.into()),
+                Ok(anzahl) => daten = &daten[anzahl..],
+                Err(fehler) if fehler.kind() == ErrorKind::WouldBlock => {
+                    thread::sleep(Duration::from_millis(1))
+                }
+                Err(fehler) => return Err(fehler),
+            }
+        }
+        Ok(())
+    }
+
+    /// Alle vollständig empfangenen Zeilen. Ein Fehler bedeutet, dass die Verbindung getrennt
+    /// ist.
+    fn empfange(&mut self) -> io::Result<Vec<String>> {
+        let mut puffer = [0; 1024];
+        loop {
+            match self.stream.read(&mut puffer) {
+                Ok(0) => return Err(ErrorKind::UnexpectedEof.into()),
+                Ok(anzahl) => self.eingang.extend_from_slice(&puffer[..anzahl]),
+                Err(fehler) if fehler.kind() == ErrorKind::WouldBlock => break,
+                Err(fehler) if fehler.kind() == ErrorKind::Interrupted => (),
+                Err(fehler) => return Err(fehler),
+            }
+        }
+        let mut zeilen = Vec::new();
+        while let Some(ende) = self.eingang.iter().position(|&byte| byte == b'\n') {
+            let zeile: Vec<u8> = self.eingang.drain(..=ende).collect();
+            zeilen.push(String::from_utf8_lossy(&zeile).trim().to_string());
+        }
+        Ok(zeilen)
+    }
+}
+
+/// Was während `poll` von der Gegenseite angekommen ist
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Ereignis {
+    /// Die Verbindung steht. Die Partie beginnt oder wird mit diesem Stand fortgesetzt.
+    Verbunden(Partie),
+    /// Der Gegner hat gezogen, das ist das Brett danach
+    Zug(SpielBrett),
+    Aufgabe,
+    /// Die Zeit des Gegners ist abgelaufen
+    Zeitverlust,
+    RemisAngebot,
+    /// Ein Remisangebot wurde angenommen
+    Remis,
+    RemisAbgelehnt,
+    /// Die Verbindung ist abgebrochen. Der Gast verbindet sich von selbst neu, der Gastgeber
+    /// wartet auf ihn.
+    Getrennt,
+    Fehler(String),
+}
+
+enum Rolle {
+    Gastgeber(TcpListener),
+    Gast(SocketAddr),
+}
+
+/// Eine Partie gegen eine andere Instanz im Netzwerk. Beide Seiten prüfen jeden Zug gegen die
+/// erlaubten Züge, der Gastgeber hat die maßgebliche Partie und schickt sie beim
+/// Wiederverbinden.
+pub struct Netzpartie {
+    rolle: Rolle,
+    verbindung: Option<Verbindung>,
+    /// Beim Gast erst bekannt, wenn der Gastgeber geantwortet hat
+    angebot: Option<Spielangebot>,
+    partie: Option<Partie>,
+    /// Die Züge seit der Startstellung in Notation
+    zuege: Vec<String>,
+    /// Ob der Handshake auf der aktuellen Verbindung abgeschlossen ist, beim Gastgeber mit
+    /// `HALLO`, beim Gast mit `VERLAUF`
+    angemeldet: bool,
+    /// Ob wir Remis angeboten haben und auf Antwort warten
+    remis_angeboten: bool,
+    /// Wer auf Zeit verloren hat. Der eigene Zeitverlust wird nach dem Wiederverbinden erneut
+    /// geschickt.
+    zeitverlust: Option<Spieler>,
+    letzter_versuch: Option<Instant>,
+}
+
+impl Netzpartie {
+    /// Wartet auf `adresse` auf einen Gast, der die Partie zu `angebot` spielt
+    pub fn hoste(adresse: impl ToSocketAddrs, angebot: Spielangebot) -> io::Result<Self> {
+        let listener = TcpListener::bind(adresse)?;
+        listener.set_nonblocking(true)?;
+        Ok(Self {
+            rolle: Rolle::Gastgeber(listener),
+            verbindung: None,
+            partie: Some(Partie::new(
+                angebot.start.clone(),
+                angebot.start_am_zug,
+                angebot.regeln,
+            )),
+            angebot: Some(angebot),
+            zuege: Vec::new(),
+            angemeldet: false,
+            remis_angeboten: false,
+            zeitverlust: None,
+            letzter_versuch: None,
+        })
+    }
+
+    /// Verbindet sich mit einem Gastgeber. Ohne Port wird `STANDARD_PORT` verwendet.
+    pub fn verbinde(adresse: &str) -> io::Result<Self> {
+        let adresse = match adresse.to_socket_addrs() {
+            Ok(mut adressen) => adressen.next(),
+            Err(_) => (adresse, STANDARD_PORT).to_socket_addrs()?.next(),
+        }
+        .ok_or(ErrorKind::AddrNotAvailable)?;
+        let mut netz = Self {
+            rolle: Rolle::Gast(adresse),
+            verbindung: None,
+            angebot: None,
+            partie: None,
+            zuege: Vec::new(),
+            angemeldet: false,
+            remis_angeboten: false,
+            zeitverlust: None,
+            letzter_versuch: None,
+        };
+        netz.verbinde_gast()?;
+        Ok(netz)
+    }
+
+    fn verbinde_gast(&mut self) -> io::Result<()> {
+        let Rolle::Gast(adresse) = self.rolle else {
+            return Ok(());
+        };
+        self.letzter_versuch = Some(Instant::now());
+        let mut verbindung =
+            Verbindung::new(TcpStream::connect_timeout(&adresse, VERBINDEN_TIMEOUT)?)?;
+        verbindung.sende(&Nachricht::Hallo {
+            version: PROTOKOLL_VERSION,
+        })?;
+        self.verbindung = Some(verbindung);
+        self.angemeldet = false;
+        Ok(())
+    }
+
+    /// Die Adresse, auf der der Gastgeber wartet
+    pub fn get_lokale_adresse(&self) -> Option<SocketAddr> {
+        match &self.rolle {
+            Rolle::Gastgeber(listener) => listener.local_addr().ok(),
+            Rolle::Gast(_) => None,
+        }
+    }
+
+    pub fn is_gastgeber(&self) -> bool {
+        matches!(self.rolle, Rolle::Gastgeber(_))
+    }
+
+    /// Ob die Gegenseite verbunden ist und die Partie kennt
+    pub fn is_verbunden(&self) -> bool {
+        self.verbindung.is_some() && self.angemeldet && self.partie.is_some()
+    }
+
+    pub fn get_angebot(&self) -> Option<&Spielangebot> {
+        self.angebot.as_ref()
+    }
+
+    /// Die Farbe, mit der diese Instanz spielt
+    pub fn get_eigene_farbe(&self) -> Option<Spieler> {
+        let gast_farbe = self.angebot.as_ref()?.gast_farbe;
+        Some(if self.is_gastgeber() {
+            !gast_farbe
+        } else {
+            gast_farbe
+        })
+    }
+
+    pub fn get_partie(&self) -> Option<&Partie> {
+        self.partie.as_ref()
+    }
+
+    pub fn get_zuege(&self) -> &[String] {
+        &self.zuege
+    }
+
+    fn sende(&mut self, nachricht: &Nachricht) -> Option<()> {
+        let verbindung = self.verbindung.as_mut()?;
+        if verbindung.sende(nachricht).is_err() {
+            // Wird beim nächsten `poll` als Trennung gemeldet
+            self.verbindung = None;
+            return None;
+        }
+        Some(())
+    }
+
+    /// Schickt einen eigenen Zug an den Gegner und spielt ihn. Gibt `None` zurück, wenn wir
+    /// nicht am Zug sind, der Zug nicht erlaubt ist oder er sich nicht schicken lässt. Der Zug
+    /// wird dann auch nicht gespielt.
+    pub fn sende_zug(&mut self, nachher: &SpielBrett) -> Option<()> {
+        let eigene_farbe = self.get_eigene_farbe()?;
+        let partie = self.partie.as_ref()?;
+        if !self.is_verbunden() || self.zeitverlust.is_some() || partie.get_am_zug() != eigene_farbe
+        {
+            return None;
+        }
+        let (zug, _) = partie
+            .get_brett()
+            .get_possible_zuege(eigene_farbe)
+            .into_iter()
+            .find(|(_, brett)| brett == nachher)?;
+        self.sende(&Nachricht::Zug {
+            nummer: self.zuege.len(),
+            notation: zug.to_notation(),
+        })?;
+        self.partie.as_mut()?.ziehe(nachher.clone())?;
+        self.zuege.push(zug.to_notation());
+        self.remis_angeboten = false;
+        Some(())
+    }
+
+    /// Meldet dem Gegner, dass unsere Zeit abgelaufen ist. Gibt `None` zurück, wenn wir nicht
+    /// am Zug sind. Ohne Verbindung wird der Zeitverlust nach dem Wiederverbinden geschickt.
+    pub fn melde_zeitverlust(&mut self) -> Option<()> {
+        let eigene_farbe = self.get_eigene_farbe()?;
+        if self.partie.as_ref()?.get_am_zug() != eigene_farbe {
+            return None;
+        }
+        self.zeitverlust = Some(eigene_farbe);
+        self.sende(&Nachricht::Zeitverlust);
+        Some(())
+    }
+
+    pub fn gib_auf(&mut self) -> Option<()> {
+        self.sende(&Nachricht::Aufgabe)
+    }
+
+    pub fn biete_remis_an(&mut self) -> Option<()> {
+        self.sende(&Nachricht::RemisAngebot)?;
+        self.remis_angeboten = true;
+        Some(())
+    }
+
+    pub fn beantworte_remis(&mut self, annehmen: bool) -> Option<()> {
+        self.sende(&if annehmen {
+            Nachricht::RemisAnnahme
+        } else {
+            Nachricht::RemisAblehnung
+        })
+    }
+
+    /// Nimmt neue Verbindungen an, verbindet den Gast nach einer Trennung neu und verarbeitet
+    /// alle empfangenen Nachrichten. Blockiert nicht.
+    pub fn poll(&mut self) -> Vec<Ereignis> {
+        let mut ereignisse = Vec::new();
+        match &self.rolle {
+            Rolle::Gastgeber(listener) => {
+                // Solange ein Gast verbunden ist, wird jeder weitere gleich wieder getrennt.
+                // Nach einer Trennung kann sich derselbe Gast neu verbinden.
+                if let Ok((stream, _)) = listener.accept() {
+                    if self.verbindung.is_none() {
+                        self.verbindung = Verbindung::new(stream).ok();
+                        self.angemeldet = false;
+                    }
+                }
+            }
+            Rolle::Gast(_) => {
+                let warten = self
+                    .letzter_versuch
+                    .is_some_and(|zeit| zeit.elapsed() < WIEDERVERBINDEN_NACH);
+                if self.verbindung.is_none() && !warten {
+                    let _ = self.verbinde_gast();
+                }
+            }
+        }
+
+        let Some(verbindung) = &mut self.verbindung else {
+            return ereignisse;
+        };
+        let zeilen = match verbindung.empfange() {
+            Ok(zeilen) => zeilen,
+            Err(_) => {
+                self.trenne();
+                ereignisse.push(Ereignis::Getrennt);
+                return ereignisse;
+            }
+        };
+        for zeile in zeilen {
+            if zeile.is_empty() {
+                continue;
+            }
+            let ereignis = match Nachricht::parse(&zeile) {
+                Some(nachricht) => self.verarbeite(nachricht),
+                None => self.melde_fehler(format!("Unbekannte Nachricht: {zeile}")),
+            };
+            ereignisse.extend(ereignis);
+        }
+        ereignisse
+    }
+
+    fn trenne(&mut self) {
+        self.verbindung = None;
+        self.angemeldet = false;
+        self.remis_angeboten = false;
+        // Der Gast versucht es sofort wieder
+        self.letzter_versuch = None;
+    }
+
+    /// Schickt einen Fehler an die Gegenseite und meldet ihn auch lokal
+    fn melde_fehler(&mut self, text: String) -> Option<Ereignis> {
+        self.sende(&Nachricht::Fehler(text.clone()));
+        Some(Ereignis::Fehler(text))
+    }
+
+    /// Schickt unseren Zeitverlust erneut, nachdem die Verbindung wieder steht
+    fn wiederhole_zeitverlust(&mut self) {
+        if self.zeitverlust.is_some() && self.zeitverlust == self.get_eigene_farbe() {
+            self.sende(&Nachricht::Zeitverlust);
+        }
+    }
+
+    fn verarbeite(&mut self, nachricht: Nachricht) -> Option<Ereignis> {
+        let handshake = matches!(
+            nachricht,
+            Nachricht::Hallo { .. }
+                | Nachricht::Spiel(_)
+                | Nachricht::Verlauf(_)
+                | Nachricht::Fehler(_)
+        );
+        if !handshake && !self.angemeldet {
+            return self.melde_fehler(format!(
+                "Nachricht vor der Anmeldung: {}",
+                nachricht.to_zeile()
+            ));
+        }
+        match nachricht {
+            Nachricht::Hallo { version } => {
+                let angebot = self.angebot.clone().filter(|_| self.is_gastgeber())?;
+                if version != PROTOKOLL_VERSION {
+                    let ereignis = self
+                        .melde_fehler(format!("Protokollversion {version} wird nicht unterstützt"));
+                    self.verbindung = None;
+                    return ereignis;
+                }
+                self.sende(&Nachricht::Spiel(angebot))?;
+                self.sende(&Nachricht::Verlauf(self.zuege.clone()))?;
+                self.angemeldet = true;
+                self.wiederhole_zeitverlust();
+                Some(Ereignis::Verbunden(self.partie.clone()?))
+            }
+            Nachricht::Spiel(angebot) => {
+                if self.is_gastgeber() {
+                    return None;
+                }
+                self.partie = Some(Partie::new(
+                    angebot.start.clone(),
+                    angebot.start_am_zug,
+                    angebot.regeln,
+                ));
+                self.angebot = Some(angebot);
+                self.zuege.clear();
+                None
+            }
+            Nachricht::Verlauf(zuege) => {
+                let angebot = self.angebot.clone().filter(|_| !self.is_gastgeber())?;
+                let mut partie =
+                    Partie::new(angebot.start.clone(), angebot.start_am_zug, angebot.regeln);
+                for notation in &zuege {
+                    let am_zug = partie.get_am_zug();
+                    let Some(nachher) = partie.get_brett().find_zug(am_zug, notation) else {
+                        return self.melde_fehler(format!("Ungültiger Zug im Verlauf: {notation}"));
+                    };
+                    if partie.ziehe(nachher).is_none() {
+                        return self
+                            .melde_fehler(format!("Zug nach Spielende im Verlauf: {notation}"));
+                    }
+                }
+                self.partie = Some(partie.clone());
+                self.zuege = zuege;
+                self.angemeldet = true;
+                self.wiederhole_zeitverlust();
+                Some(Ereignis::Verbunden(partie))
+            }
+            Nachricht::Zug { .. } if self.zeitverlust.is_some() => {
+                self.melde_fehler("Zug nach Zeitverlust".to_string())
+            }
+            Nachricht::Zug { nummer, notation } => {
+                if nummer != self.zuege.len() {
+                    return self.melde_fehler(format!(
+                        "Zug {nummer} passt nicht, erwartet wird Zug {}",
+                        self.zuege.len()
+                    ));
+                }
+                let gegner = !self.get_eigene_farbe()?;
+                let partie = self.partie.as_mut()?;
+                let nachher = (partie.get_am_zug() == gegner)
+                    .then(|| partie.get_brett().find_zug(gegner, &notation))
+                    .flatten();
+                let Some(nachher) = nachher else {
+                    return self.melde_fehler(format!("Ungültiger Zug: {notation}"));
+                };
+                let zug = partie.get_brett().get_zug(&nachher, gegner)?;
+                partie.ziehe(nachher.clone())?;
+                self.zuege.push(zug.to_notation());
+                Some(Ereignis::Zug(nachher))
+            }
+            Nachricht::Aufgabe => Some(Ereignis::Aufgabe),
+            Nachricht::Zeitverlust => {
+                let gegner = !self.get_eigene_farbe()?;
+                if self.partie.as_ref()?.get_am_zug() != gegner {
+                    return self.melde_fehler("Zeitverlust, ohne am Zug zu sein".to_string());
+                }
+                self.zeitverlust = Some(gegner);
+                Some(Ereignis::Zeitverlust)
+            }
+            Nachricht::RemisAngebot => Some(Ereignis::RemisAngebot),
+            Nachricht::RemisAnnahme if self.remis_angeboten => {
+                self.remis_angeboten = false;
+                Some(Ereignis::Remis)
+            }
+            Nachricht::RemisAnnahme => None,
+            Nachricht::RemisAblehnung => {
+                self.remis_angeboten = false;
+                Some(Ereignis::RemisAbgelehnt)
+            }
+            Nachricht::Fehler(text) => Some(Ereignis::Fehler(text)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fragt beide Seiten ab, bis `fertig` für die gesammelten Ereignisse erfüllt ist
+    fn warte(
+        gastgeber: &mut Netzpartie,
+        gast: &mut Netzpartie,
+        fertig: impl Fn(&[Ereignis], &[Ereignis]) -> bool,
+    ) -> (Vec<Ereignis>, Vec<Ereignis>) {
+        let start = Instant::now();
+        let (mut beim_gastgeber, mut beim_gast) = (Vec::new(), Vec::new());
+        while !fertig(&beim_gastgeber, &beim_gast) {
+            assert!(
+                start.elapsed() < Duration::from_secs(5),
+                "Zeitüberschreitung"
+            );
+            beim_gastgeber.extend(gastgeber.poll());
+            beim_gast.extend(gast.poll());
+            thread::sleep(Duration::from_millis(1));
+        }
+        (beim_gastgeber, beim_gast)
+    }
+
+    fn get_zug(partie: &Partie, notation: &str) -> SpielBrett {
+        partie
+            .get_brett()
+            .find_zug(partie.get_am_zug(), notation)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_nachricht() {
+        let mut angebot = Spielangebot::new(Spieler::Schwarz);
+        angebot.zeitkontrolle = Some(Zeitkontrolle::Zuschlag {
+            grundzeit: Duration::from_secs(300),
+            zuschlag: Duration::from_secs(3),
+        });
+        angebot.regeln.max_zuege_ohne_fortschritt = None;
+        for nachricht in [
+            Nachricht::Hallo { version: 1 },
+            Nachricht::Spiel(angebot),
+            Nachricht::Verlauf(Vec::new()),
+            Nachricht::Verlauf(vec!["11-15".to_string(), "23-19".to_string()]),
+            Nachricht::Zug {
+                nummer: 3,
+                notation: "22x15".to_string(),
+            },
+            Nachricht::Zeitverlust,
+            Nachricht::RemisAngebot,
+            Nachricht::Fehler("Ungültiger Zug: 1-2".to_string()),
+        ] {
+            assert_eq!(Nachricht::parse(&nachricht.to_zeile()), Some(nachricht));
+        }
+        assert_eq!(
+            Nachricht::parse("SPIEL variante=international gast=weiss"),
+            None
+        );
+        assert_eq!(Nachricht::parse("ZUG 1"), None);
+    }
+
+    #[test]
+    fn test_netzpartie() {
+        let mut angebot = Spielangebot::new(Spieler::Weiss);
+        angebot.zeitkontrolle = Some(Zeitkontrolle::ProZug(Duration::from_secs(10)));
+        let mut gastgeber = Netzpartie::hoste("127.0.0.1:0", angebot.clone()).unwrap();
+        let adresse = gastgeber.get_lokale_adresse().unwrap().to_string();
+        let mut gast = Netzpartie::verbinde(&adresse).unwrap();
+        warte(&mut gastgeber, &mut gast, |_, gast| !gast.is_empty());
+        assert_eq!(gast.get_angebot(), Some(&angebot));
+        assert_eq!(gastgeber.get_eigene_farbe(), Some(Spieler::Schwarz));
+        assert_eq!(gast.get_eigene_farbe(), Some(Spieler::Weiss));
+
+        // Nicht am Zug und ungültige Züge werden nicht verschickt
+        let start = gast.get_partie().unwrap().clone();
+        assert_eq!(gast.sende_zug(&get_zug(&start, "11-15")), None);
+        assert_eq!(gastgeber.sende_zug(&SpielBrett::default()), None);
+
+        let nachher = get_zug(&start, "11-15");
+        gastgeber.sende_zug(&nachher).unwrap();
+        let (_, beim_gast) = warte(&mut gastgeber, &mut gast, |_, gast| !gast.is_empty());
+        assert_eq!(beim_gast, vec![Ereignis::Zug(nachher)]);
+
+        // Ein Zug, den der Gegner nicht machen darf, wird abgelehnt
+        gast.sende(&Nachricht::Zug {
+            nummer: 1,
+            notation: "11-16".to_string(),
+        });
+        let (beim_gastgeber, beim_gast) = warte(&mut gastgeber, &mut gast, |gastgeber, gast| {
+            !gastgeber.is_empty() && !gast.is_empty()
+        });
+        assert_eq!(
+            beim_gastgeber,
+            vec![Ereignis::Fehler("Ungültiger Zug: 11-16".to_string())]
+        );
+        assert_eq!(beim_gast, beim_gastgeber);
+
+        gast.biete_remis_an().unwrap();
+        warte(&mut gastgeber, &mut gast, |gastgeber, _| {
+            gastgeber == [Ereignis::RemisAngebot]
+        });
+        gastgeber.beantworte_remis(false).unwrap();
+        warte(&mut gastgeber, &mut gast, |_, gast| {
+            gast == [Ereignis::RemisAbgelehnt]
+        });
+
+        // Ein zweiter Gast wird abgewiesen, der erste spielt weiter
+        let mut zweiter = Netzpartie::verbinde(&adresse).unwrap();
+        let (_, beim_zweiten) = warte(&mut gastgeber, &mut zweiter, |_, zweiter| {
+            !zweiter.is_empty()
+        });
+        assert_eq!(beim_zweiten, vec![Ereignis::Getrennt]);
+        assert_eq!(zweiter.get_partie(), None);
+        gast.sende_zug(&get_zug(gast.get_partie().unwrap(), "24-20"))
+            .unwrap();
+        let (beim_gastgeber, _) = warte(&mut gastgeber, &mut gast, |gastgeber, _| {
+            !gastgeber.is_empty()
+        });
+        assert!(matches!(beim_gastgeber[..], [Ereignis::Zug(_)]));
+        drop(zweiter);
+
+        // Nach einer Trennung verbindet sich der Gast neu und bekommt den Verlauf
+        gastgeber.trenne();
+        let partie = gastgeber.get_partie().unwrap().clone();
+        let (_, nachher) = partie
+            .get_brett()
+            .get_possible_zuege(partie.get_am_zug())
+            .remove(0);
+        assert_eq!(gastgeber.sende_zug(&nachher), None);
+        let (_, beim_gast) = warte(&mut gastgeber, &mut gast, |_, gast| {
+            matches!(gast.last(), Some(Ereignis::Verbunden(_)))
+        });
+        assert_eq!(beim_gast[0], Ereignis::Getrennt);
+        assert_eq!(gast.get_zuege(), ["11-15", "24-20"]);
+        assert_eq!(gast.get_partie(), gastgeber.get_partie());
+
+        // Den Zeitverlust meldet nur, wer am Zug ist, und er gilt auch nach dem Wiederverbinden
+        assert_eq!(gast.melde_zeitverlust(), None);
+        gastgeber.melde_zeitverlust().unwrap();
+        warte(&mut gastgeber, &mut gast, |_, gast| {
+            gast == [Ereignis::Zeitverlust]
+        });
+        assert_eq!(gastgeber.sende_zug(&nachher), None);
+        gastgeber.trenne();
+        let (_, beim_gast) = warte(&mut gastgeber, &mut gast, |_, gast| {
+            gast.last() == Some(&Ereignis::Zeitverlust)
+        });
+        assert!(matches!(
+            beim_gast[..],
+            [.., Ereignis::Verbunden(_), Ereignis::Zeitverlust]
+        ));
+
+        gast.gib_auf().unwrap();
+        warte(&mut gastgeber, &mut gast, |gastgeber, _| {
+            gastgeber == [Ereignis::Aufgabe]
+        });
+    }
+
+    #[test]
+    fn test_zug_vor_hallo() {
+        let mut gastgeber =
+            Netzpartie::hoste("127.0.0.1:0", Spielangebot::new(Spieler::Schwarz)).unwrap();
+        let adresse = gastgeber.get_lokale_adresse().unwrap();
+        let mut stream = TcpStream::connect(adresse).unwrap();
+        stream.write_all(b"ZUG 0 11-15\n").unwrap();
+        let mut ereignisse = Vec::new();
+        let start = Instant::now();
+        while ereignisse.is_empty() {
+            assert!(
+                start.elapsed() < Duration::from_secs(5),
+                "Zeitüberschreitung"
+            );
+            ereignisse.extend(gastgeber.poll());
+            thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(
+            ereignisse,
+            vec![Ereignis::Fehler(
+                "Nachricht vor der Anmeldung: ZUG 0 11-15".to_string()
+            )]
+        );
+        assert!(gastgeber.get_zuege().is_empty());
+        assert!(!gastgeber.is_verbunden());
+    }
+
+    #[test]
+    fn test_verlauf_nach_spielende() {
+        let mut angebot = Spielangebot::new(Spieler::Weiss);
+        angebot.regeln.max_zuege_ohne_fortschritt = Some(1);
+        angebot.start = SpielBrett::parse(concat!(
+            "_ _ _ M \n",
+            " _ _ _ _\n",
+            "_ _ _ _ \n",
+            " _ _ _ _\n",
+            "_ _ _ _ \n",
+            " _ _ _ _\n",
+            "_ _ _ _ \n",
+            " C _ _ _",
+        ))
+        .unwrap();
+        angebot.start_am_zug = Spieler::Weiss;
+        let mut gast = Netzpartie {
+            rolle: Rolle::Gast(([127, 0, 0, 1], STANDARD_PORT).into()),
+            verbindung: None,
+            angebot: Some(angebot),
+            partie: None,
+            zuege: Vec::new(),
+            angemeldet: false,
+            remis_angeboten: false,
+            zeitverlust: None,
+            letzter_versuch: None,
+        };
+        // Nach zwei Halbzügen ohne Fortschritt ist die Partie schon remis
+        let zuege = ["29-25", "4-8", "25-29"].map(String::from).to_vec();
+        assert_eq!(
+            gast.verarbeite(Nachricht::Verlauf(zuege)),
+            Some(Ereignis::Fehler(
+                "Zug nach Spielende im Verlauf: 25-29".to_string()
+            ))
+        );
+        assert_eq!(gast.get_partie(), None);
+    }
+}
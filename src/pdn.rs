@@ -0,0 +1,355 @@
+use crate::brett::{Feld, Position, SpielBrett, Spieler};
+
+// In PDN beginnt Schwarz (B) oben auf den Feldern 1 bis 12, Weiß (W) steht unten.
+fn spieler_to_farbe(spieler: Spieler) -> char {
+    match spieler {
+        Spieler::Schwarz => 'B',
+        Spieler::Weiss => 'W',
+    }
+}
+
+fn farbe_to_spieler(farbe: char) -> Option<Spieler> {
+    match farbe {
+        'B' | 'b' => Some(Spieler::Schwarz),
+        'W' | 'w' => Some(Spieler::Weiss),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Ergebnis {
+    Sieg(Spieler),
+    Unentschieden,
+    Offen,
+}
+
+impl Ergebnis {
+    fn parse(text: &str) -> Option<Self> {
+        Some(match text {
+            "2-0" | "1-0" => Self::Sieg(Spieler::Weiss),
+            "0-2" | "0-1" => Self::Sieg(Spieler::Schwarz),
+            "1-1" | "1/2-1/2" => Self::Unentschieden,
+            "*" => Self::Offen,
+            _ => return None,
+        })
+    }
+
+    pub fn to_pdn(self) -> &'static str {
+        match self {
+            Self::Sieg(Spieler::Weiss) => "2-0",
+            Self::Sieg(Spieler::Schwarz) => "0-2",
+            Self::Unentschieden => "1-1",
+            Self::Offen => "*",
+        }
+    }
+}
+
+pub fn parse_fen(fen: &str) -> Option<(SpielBrett, Spieler)> {
+    let mut teile = fen.trim().trim_end_matches('.').split(':');
+    let am_zug = farbe_to_spieler(teile.next()?.trim().chars().next()?)?;
+    let mut brett = SpielBrett::default();
+    for teil in teile {
+        let mut zeichen = teil.trim().chars();
+        let spieler = farbe_to_spieler(zeichen.next()?)?;
+        for feld in zeichen.as_str().split(',') {
+            let feld = feld.trim();
+            if feld.is_empty() {
+                continue;
+            }
+            let (dame, nummer) = match feld.strip_prefix('K') {
+                Some(nummer) => (true, nummer),
+                None => (false, feld),
+            };
+            let position = Position::from_feld_nummer(nummer.parse().ok()?)?;
+            brett.set(
+                position,
+                if dame {
+                    Feld::Dame(spieler)
+                } else {
+                    Feld::Stein(spieler)
+                },
+            );
+        }
+    }
+    Some((brett, am_zug))
+}
+
+pub fn to_fen(brett: &SpielBrett, am_zug: Spieler) -> String {
+    let mut result = String::new();
+    result.push(spieler_to_farbe(am_zug));
+    for spieler in [Spieler::Weiss, Spieler::Schwarz] {
+        let mut felder = Vec::new();
+        for nummer in 1..=SpielBrett::SIZE * SpielBrett::SIZE / 2 {
+            match brett.get(Position::from_feld_nummer(nummer).unwrap()) {
+                Feld::Stein(stein_spieler) if stein_spieler == spieler => {
+                    felder.push(nummer.to_string())
+                }
+                Feld::Dame(dame_spieler) if dame_spieler == spieler => {
+                    felder.push(format!("K{nummer}"))
+                }
+                _ => (),
+            }
+        }
+        result.push(':');
+        result.push(spieler_to_farbe(spieler));
+        result.push_str(&felder.join(","));
+    }
+    result
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PdnSpiel {
+    pub tags: Vec<(String, String)>,
+    pub start: SpielBrett,
+    pub start_am_zug: Spieler,
+    /// Das Brett nach jedem Zug
+    pub stellungen: Vec<SpielBrett>,
+    pub ergebnis: Ergebnis,
+}
+
+impl PdnSpiel {
+    pub fn new(start: SpielBrett, start_am_zug: Spieler) -> Self {
+        Self {
+            tags: Vec::new(),
+            start,
+            start_am_zug,
+            stellungen: Vec::new(),
+            ergebnis: Ergebnis::Offen,
+        }
+    }
+
+    pub fn get_tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(tag_name, _)| tag_name == name)
+            .map(|(_, wert)| wert.as_str())
+    }
+
+    pub fn set_tag(&mut self, name: &str, wert: &str) {
+        match self.tags.iter_mut().find(|(tag_name, _)| tag_name == name) {
+            Some((_, alter_wert)) => *alter_wert = wert.to_string(),
+            None => self.tags.push((name.to_string(), wert.to_string())),
+        }
+    }
+
+    pub fn get_brett(&self) -> &SpielBrett {
+        self.stellungen.last().unwrap_or(&self.start)
+    }
+
+    pub fn get_am_zug(&self) -> Spieler {
+        if self.stellungen.len().is_multiple_of(2) {
+            self.start_am_zug
+        } else {
+            !self.start_am_zug
+        }
+    }
+
+    /// Führt einen Zug in Notation aus, falls er in der aktuellen Stellung erlaubt ist.
+    pub fn push_zug(&mut self, notation: &str) -> Option<()> {
+        let brett = self.get_brett().find_zug(self.get_am_zug(), notation)?;
+        self.stellungen.push(brett);
+        Some(())
+    }
+
+    /// Jede Stellung vor einem Zug zusammen mit dem Spieler am Zug und dem Brett danach
+    pub fn get_zuege(&self) -> Vec<(&SpielBrett, Spieler, &SpielBrett)> {
+        let mut vorher = &self.start;
+        let mut am_zug = self.start_am_zug;
+        let mut result = Vec::new();
+        for nachher in &self.stellungen {
+            result.push((vorher, am_zug, nachher));
+            vorher = nachher;
+            am_zug = !am_zug;
+        }
+        result
+    }
+
+    fn parse_tag(zeile: &str) -> Option<(String, String)> {
+        let inhalt = zeile.trim().strip_prefix('[')?.strip_suffix(']')?;
+        let (name, wert) = inhalt.split_once(' ')?;
+        let wert = wert.trim().strip_prefix('"')?.strip_suffix('"')?;
+        Some((name.trim().to_string(), wert.to_string()))
+    }
+
+    /// Liest alle Spiele aus einer PDN-Datei. Gibt `None` zurück, wenn ein Spiel einen
+    /// unerlaubten Zug enthält.
+    pub fn parse_alle(text: &str) -> Option<Vec<Self>> {
+        let mut spiele = Vec::new();
+        let mut tags = Vec::new();
+        let mut zuege_text = String::new();
+
+        let mut zeichen = text.chars();
+        while let Some(c) = zeichen.next() {
+            match c {
+                '[' => {
+                    if !zuege_text.trim().is_empty() {
+                        spiele.push(Self::parse_spiel(std::mem::take(&mut tags), &zuege_text)?);
+                        zuege_text.clear();
+                    }
+                    let mut zeile = String::from(c);
+                    for c in zeichen.by_ref() {
+                        zeile.push(c);
+                        if c == ']' {
+                            break;
+                        }
+                    }
+                    tags.push(Self::parse_tag(&zeile)?);
+                }
+                '{' => {
+                    for c in zeichen.by_ref() {
+                        if c == '}' {
+                            break;
+                        }
+                    }
+                    zuege_text.push(' ');
+                }
+                '(' => {
+                    let mut tiefe = 1;
+                    for c in zeichen.by_ref() {
+                        match c {
+                            '(' => tiefe += 1,
+                            ')' => tiefe -= 1,
+                            _ => (),
+                        }
+                        if tiefe == 0 {
+                            break;
+                        }
+                    }
+                    zuege_text.push(' ');
+                }
+                ';' => {
+                    for c in zeichen.by_ref() {
+                        if c == '\n' {
+                            break;
+                        }
+                    }
+                    zuege_text.push(' ');
+                }
+                c => zuege_text.push(c),
+            }
+        }
+
+        if !tags.is_empty() || !zuege_text.trim().is_empty() {
+            spiele.push(Self::parse_spiel(tags, &zuege_text)?);
+        }
+
+        Some(spiele)
+    }
+
+    fn parse_spiel(tags: Vec<(String, String)>, zuege_text: &str) -> Option<Self> {
+        let (start, start_am_zug) = match tags.iter().find(|(name, _)| name == "FEN") {
+            Some((_, fen)) => parse_fen(fen)?,
+            None => (SpielBrett::startaufstellung(), Spieler::Schwarz),
+        };
+        let mut spiel = Self::new(start, start_am_zug);
+        spiel.tags = tags;
+
+        for token in zuege_text.split_whitespace() {
+            if let Some(ergebnis) = Ergebnis::parse(token) {
+                spiel.ergebnis = ergebnis;
+                break;
+            }
+            // Zugnummern wie "1." oder "1..." und Anmerkungen wie "!?" überspringen
+            let token = match token.rfind('.') {
+                Some(index) => &token[index + 1..],
+                None => token,
+            };
+            let token = token.trim_end_matches(['!', '?', '*', '+']);
+            if token.is_empty() || token.starts_with('$') {
+                continue;
+            }
+            spiel.push_zug(token)?;
+        }
+
+        if spiel.ergebnis == Ergebnis::Offen {
+            if let Some(ergebnis) = spiel.get_tag("Result").and_then(Ergebnis::parse) {
+                spiel.ergebnis = ergebnis;
+            }
+        }
+
+        Some(spiel)
+    }
+
+    pub fn to_pdn(&self) -> String {
+        let mut result = String::new();
+        let mut tags = self.tags.clone();
+        if !tags.iter().any(|(name, _)| name == "Result") {
+            tags.push(("Result".to_string(), self.ergebnis.to_pdn().to_string()));
+        }
+        let startaufstellung =
+            self.start == SpielBrett::startaufstellung() && self.start_am_zug == Spieler::Schwarz;
+        if !startaufstellung && !tags.iter().any(|(name, _)| name == "FEN") {
+            tags.push(("FEN".to_string(), to_fen(&self.start, self.start_am_zug)));
+        }
+        for (name, wert) in &tags {
+            result.push_str(&format!("[{name} \"{wert}\"]\n"));
+        }
+        result.push('\n');
+
+        let mut zeile = String::new();
+        let erster_zug_nummer_offset = usize::from(self.start_am_zug != Spieler::Schwarz);
+        for (index, (vorher, am_zug, nachher)) in self.get_zuege().into_iter().enumerate() {
+            let halbzug = index + erster_zug_nummer_offset;
+            if halbzug.is_multiple_of(2) {
+                zeile.push_str(&format!("{}. ", halbzug / 2 + 1));
+            } else if index == 0 {
+                zeile.push_str(&format!("{}... ", halbzug / 2 + 1));
+            }
+            let notation = match vorher.get_zug(nachher, am_zug) {
+                Some(zug) => zug.to_notation(),
+                None => break,
+            };
+            zeile.push_str(&notation);
+            zeile.push(' ');
+            if zeile.len() > 70 {
+                result.push_str(zeile.trim_end());
+                result.push('\n');
+                zeile.clear();
+            }
+        }
+        zeile.push_str(self.ergebnis.to_pdn());
+        result.push_str(&zeile);
+        result.push('\n');
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pdn_parse() {
+        let spiele = PdnSpiel::parse_alle(concat!(
+            "[Event \"Test\"]\n",
+            "[Result \"2-0\"]\n",
+            "\n",
+            "1. 10-14 {Eröffnung} 23-19 2. 14x23 (2. 9-13) 27x20 2-0\n",
+            "\n",
+            "[Event \"Zweites Spiel\"]\n",
+            "[FEN \"W:W30:BK1\"]\n",
+            "\n",
+            "1... 30-26 1-1\n",
+        ))
+        .unwrap();
+
+        assert_eq!(spiele.len(), 2);
+        assert_eq!(spiele[0].get_tag("Event"), Some("Test"));
+        assert_eq!(spiele[0].stellungen.len(), 4);
+        assert_eq!(spiele[0].ergebnis, Ergebnis::Sieg(Spieler::Weiss));
+        assert_eq!(spiele[0].get_am_zug(), Spieler::Schwarz);
+
+        assert_eq!(spiele[1].start_am_zug, Spieler::Weiss);
+        assert_eq!(spiele[1].stellungen.len(), 1);
+        assert_eq!(spiele[1].ergebnis, Ergebnis::Unentschieden);
+        assert_eq!(
+            to_fen(spiele[1].get_brett(), spiele[1].get_am_zug()),
+            "B:W26:BK1"
+        );
+
+        let nochmal = PdnSpiel::parse_alle(&spiele[0].to_pdn()).unwrap();
+        assert_eq!(nochmal, vec![spiele[0].clone()]);
+
+        assert_eq!(PdnSpiel::parse_alle("1. 10-15 *"), None);
+    }
+}
@@ -0,0 +1,150 @@
+use std::f32::consts::TAU;
+
+/// Samples pro Sekunde, in denen die Geräusche erzeugt werden
+pub const ABTASTRATE: u32 = 44100;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Geraeusch {
+    Zug,
+    Schlagen,
+    /// Ein Stein wird zur Dame
+    Dame,
+    Sieg,
+    Niederlage,
+}
+
+/// Ein Sinuston, der nach dem Anschlag mit der Zeitkonstante `abklingen` in Sekunden leiser wird
+fn get_ton(frequenz: f32, dauer: f32, abklingen: f32) -> Vec<f32> {
+    let anzahl = (dauer * ABTASTRATE as f32) as usize;
+    (0..anzahl)
+        .map(|index| {
+            let zeit = index as f32 / ABTASTRATE as f32;
+            // Kurz einblenden, damit es am Anfang nicht knackt
+            let einblenden = (zeit * 1000.0).min(1.0);
+            (TAU * frequenz * zeit).sin() * (-zeit / abklingen).exp() * einblenden
+        })
+        .collect()
+}
+
+/// Addiert `samples` ab `start` Sekunden zu `ziel`
+fn mische_ein(ziel: &mut Vec<f32>, start: f32, samples: &[f32], lautstaerke: f32) {
+    let start = (start * ABTASTRATE as f32) as usize;
+    if ziel.len() < start + samples.len() {
+        ziel.resize(start + samples.len(), 0.0);
+    }
+    for (ziel, sample) in ziel[start..].iter_mut().zip(samples) {
+        *ziel += sample * lautstaerke;
+    }
+}
+
+/// Eine Folge von Tönen mit den Frequenzen `noten`, die im Abstand `abstand` anschlagen
+fn get_melodie(noten: &[f32], abstand: f32) -> Vec<f32> {
+    let mut result = Vec::new();
+    for (index, &frequenz) in noten.iter().enumerate() {
+        let dauer = if index + 1 == noten.len() { 0.6 } else { 0.3 };
+        let ton = get_ton(frequenz, dauer, dauer / 3.0);
+        mische_ein(&mut result, index as f32 * abstand, &ton, 0.4);
+    }
+    result
+}
+
+impl Geraeusch {
+    pub const ALLE: [Self; 5] = [
+        Self::Zug,
+        Self::Schlagen,
+        Self::Dame,
+        Self::Sieg,
+        Self::Niederlage,
+    ];
+
+    /// Erzeugt das Geräusch als Mono-Samples zwischen -1 und 1
+    pub fn get_samples(self) -> Vec<f32> {
+        let mut result = Vec::new();
+        match self {
+            Self::Zug => {
+                // Ein hoher, schnell verklingender Anteil klingt nach Holz
+                mische_ein(&mut result, 0.0, &get_ton(1800.0, 0.05, 0.006), 0.5);
+                mische_ein(&mut result, 0.0, &get_ton(420.0, 0.08, 0.015), 0.4);
+            }
+            Self::Schlagen => {
+                for start in [0.0, 0.07] {
+                    mische_ein(&mut result, start, &get_ton(1500.0, 0.05, 0.006), 0.45);
+                    mische_ein(&mut result, start, &get_ton(160.0, 0.15, 0.04), 0.5);
+                }
+            }
+            Self::Dame => {
+                mische_ein(&mut result, 0.0, &get_ton(660.0, 0.2, 0.07), 0.4);
+                mische_ein(&mut result, 0.1, &get_ton(990.0, 0.35, 0.1), 0.4);
+            }
+            Self::Sieg => result = get_melodie(&[523.25, 659.25, 783.99, 1046.5], 0.12),
+            Self::Niederlage => result = get_melodie(&[392.0, 329.63, 261.63], 0.25),
+        }
+        for sample in &mut result {
+            *sample = sample.clamp(-1.0, 1.0);
+        }
+        result
+    }
+}
+
+/// Spielt Geräusche ab. Ohne das Feature `ton` oder ohne Audiogerät bleibt es still.
+pub struct Lautsprecher {
+    #[cfg(feature = "ton")]
+    ausgabe: Option<(rodio::OutputStream, rodio::OutputStreamHandle)>,
+}
+
+#[cfg(feature = "ton")]
+impl Lautsprecher {
+    pub fn open() -> Self {
+        Self {
+            ausgabe: rodio::OutputStream::try_default().ok(),
+        }
+    }
+
+    pub fn is_verfuegbar(&self) -> bool {
+        self.ausgabe.is_some()
+    }
+
+    pub fn spiele(&self, geraeusch: Geraeusch) {
+        if let Some((_, handle)) = &self.ausgabe {
+            let quelle = rodio::buffer::SamplesBuffer::new(1, ABTASTRATE, geraeusch.get_samples());
+            // Ein Geräusch, das nicht abgespielt werden kann, stört das Spiel nicht
+            let _ = handle.play_raw(quelle);
+        }
+    }
+}
+
+#[cfg(not(feature = "ton"))]
+impl Lautsprecher {
+    pub fn open() -> Self {
+        Self {}
+    }
+
+    pub fn is_verfuegbar(&self) -> bool {
+        false
+    }
+
+    pub fn spiele(&self, _geraeusch: Geraeusch) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_geraeusche() {
+        for geraeusch in Geraeusch::ALLE {
+            let samples = geraeusch.get_samples();
+            assert!(!samples.is_empty());
+            assert!(samples.len() < ABTASTRATE as usize * 2);
+            assert!(samples.iter().all(|sample| sample.abs() <= 1.0));
+            assert!(samples.iter().any(|sample| sample.abs() > 0.1));
+        }
+        assert!(Geraeusch::Schlagen.get_samples().len() > Geraeusch::Zug.get_samples().len());
+
+        // Ohne Audiogerät wird einfach nichts abgespielt
+        let lautsprecher = Lautsprecher::open();
+        if !lautsprecher.is_verfuegbar() {
+            lautsprecher.spiele(Geraeusch::Zug);
+        }
+    }
+}
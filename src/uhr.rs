@@ -0,0 +1,185 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    time::Duration,
+};
+
+use crate::brett::Spieler;
+
+/// Wie viel Bedenkzeit die Spieler haben
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Zeitkontrolle {
+    /// Eine Grundzeit für die ganze Partie, nach jedem Zug kommt der Zuschlag dazu
+    Zuschlag {
+        grundzeit: Duration,
+        zuschlag: Duration,
+    },
+    /// Eine feste Zeit für jeden Zug, nicht verbrauchte Zeit verfällt
+    ProZug(Duration),
+}
+
+impl Display for Zeitkontrolle {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Zuschlag {
+                grundzeit,
+                zuschlag,
+            } => write!(
+                f,
+                "{} min + {} s",
+                grundzeit.as_secs() / 60,
+                zuschlag.as_secs()
+            ),
+            Self::ProZug(zeit) => write!(f, "{} s pro Zug", zeit.as_secs()),
+        }
+    }
+}
+
+impl Zeitkontrolle {
+    /// Liest die Kurzform `300+3` mit Grundzeit und Zuschlag oder `10/zug` in Sekunden
+    pub fn parse(text: &str) -> Option<Self> {
+        if let Some(zeit) = text.trim().strip_suffix("/zug") {
+            return Some(Self::ProZug(Duration::from_secs(zeit.parse().ok()?)));
+        }
+        let (grundzeit, zuschlag) = text.trim().split_once('+')?;
+        Some(Self::Zuschlag {
+            grundzeit: Duration::from_secs(grundzeit.parse().ok()?),
+            zuschlag: Duration::from_secs(zuschlag.parse().ok()?),
+        })
+    }
+
+    /// Die Kurzform, die `parse` liest
+    pub fn to_kurzform(self) -> String {
+        match self {
+            Self::Zuschlag {
+                grundzeit,
+                zuschlag,
+            } => format!("{}+{}", grundzeit.as_secs(), zuschlag.as_secs()),
+            Self::ProZug(zeit) => format!("{}/zug", zeit.as_secs()),
+        }
+    }
+}
+
+/// Eine Schachuhr für beide Spieler. Die Zeit läuft nicht von selbst ab, sondern wird dem
+/// Spieler am Zug mit `vergehe` abgezogen.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Uhr {
+    kontrolle: Zeitkontrolle,
+    restzeit_schwarz: Duration,
+    restzeit_weiss: Duration,
+}
+
+impl Uhr {
+    pub fn new(kontrolle: Zeitkontrolle) -> Self {
+        let zeit = match kontrolle {
+            Zeitkontrolle::Zuschlag { grundzeit, .. } => grundzeit,
+            Zeitkontrolle::ProZug(zeit) => zeit,
+        };
+        Self {
+            kontrolle,
+            restzeit_schwarz: zeit,
+            restzeit_weiss: zeit,
+        }
+    }
+
+    pub fn get_kontrolle(&self) -> Zeitkontrolle {
+        self.kontrolle
+    }
+
+    fn get_restzeit_mut(&mut self, spieler: Spieler) -> &mut Duration {
+        match spieler {
+            Spieler::Schwarz => &mut self.restzeit_schwarz,
+            Spieler::Weiss => &mut self.restzeit_weiss,
+        }
+    }
+
+    pub fn get_restzeit(&self, spieler: Spieler) -> Duration {
+        match spieler {
+            Spieler::Schwarz => self.restzeit_schwarz,
+            Spieler::Weiss => self.restzeit_weiss,
+        }
+    }
+
+    /// Wessen Zeit abgelaufen ist, hat verloren
+    pub fn is_abgelaufen(&self, spieler: Spieler) -> bool {
+        self.get_restzeit(spieler).is_zero()
+    }
+
+    pub fn vergehe(&mut self, spieler: Spieler, vergangen: Duration) {
+        let restzeit = self.get_restzeit_mut(spieler);
+        *restzeit = restzeit.saturating_sub(vergangen);
+    }
+
+    /// Schreibt `spieler` nach seinem Zug den Zuschlag gut oder stellt die Zeit für den
+    /// nächsten Zug wieder her
+    pub fn zug_beendet(&mut self, spieler: Spieler) {
+        if self.is_abgelaufen(spieler) {
+            return;
+        }
+        match self.kontrolle {
+            Zeitkontrolle::Zuschlag { zuschlag, .. } => *self.get_restzeit_mut(spieler) += zuschlag,
+            Zeitkontrolle::ProZug(zeit) => *self.get_restzeit_mut(spieler) = zeit,
+        }
+    }
+
+    /// Wie lange `spieler` über seinen nächsten Zug nachdenken sollte, mit etwas Reserve, weil
+    /// eine Suche nicht sofort abbricht
+    pub fn get_zeit_fuer_zug(&self, spieler: Spieler) -> Duration {
+        let restzeit = self.get_restzeit(spieler);
+        match self.kontrolle {
+            Zeitkontrolle::Zuschlag { zuschlag, .. } => {
+                (restzeit / 25 + zuschlag * 3 / 4).min(restzeit / 3)
+            }
+            Zeitkontrolle::ProZug(_) => restzeit * 4 / 5,
+        }
+    }
+}
+
+/// Formatiert eine Restzeit als `m:ss`, unter zehn Sekunden mit Zehnteln
+pub fn format_zeit(zeit: Duration) -> String {
+    let sekunden = zeit.as_secs();
+    if sekunden < 10 {
+        format!("0:0{sekunden}.{}", zeit.subsec_millis() / 100)
+    } else {
+        format!("{}:{:02}", sekunden / 60, sekunden % 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uhr() {
+        let kontrolle = Zeitkontrolle::Zuschlag {
+            grundzeit: Duration::from_secs(300),
+            zuschlag: Duration::from_secs(3),
+        };
+        assert_eq!(kontrolle.to_string(), "5 min + 3 s");
+        assert_eq!(kontrolle.to_kurzform(), "300+3");
+        assert_eq!(Zeitkontrolle::parse("300+3"), Some(kontrolle));
+        assert_eq!(
+            Zeitkontrolle::parse("10/zug"),
+            Some(Zeitkontrolle::ProZug(Duration::from_secs(10)))
+        );
+        assert_eq!(Zeitkontrolle::parse("300"), None);
+        let mut uhr = Uhr::new(kontrolle);
+        uhr.vergehe(Spieler::Schwarz, Duration::from_secs(10));
+        uhr.zug_beendet(Spieler::Schwarz);
+        assert_eq!(uhr.get_restzeit(Spieler::Schwarz), Duration::from_secs(293));
+        assert_eq!(uhr.get_restzeit(Spieler::Weiss), Duration::from_secs(300));
+        assert!(uhr.get_zeit_fuer_zug(Spieler::Weiss) < Duration::from_secs(100));
+
+        uhr.vergehe(Spieler::Weiss, Duration::from_secs(400));
+        assert!(uhr.is_abgelaufen(Spieler::Weiss));
+        // Nach Ablauf der Zeit gibt es keinen Zuschlag mehr
+        uhr.zug_beendet(Spieler::Weiss);
+        assert!(uhr.is_abgelaufen(Spieler::Weiss));
+
+        let mut uhr = Uhr::new(Zeitkontrolle::ProZug(Duration::from_secs(10)));
+        uhr.vergehe(Spieler::Schwarz, Duration::from_secs(4));
+        assert_eq!(format_zeit(uhr.get_restzeit(Spieler::Schwarz)), "0:06.0");
+        uhr.zug_beendet(Spieler::Schwarz);
+        assert_eq!(format_zeit(uhr.get_restzeit(Spieler::Schwarz)), "0:10");
+        assert_eq!(format_zeit(Duration::from_secs(754)), "12:34");
+    }
+}
@@ -0,0 +1,167 @@
+use std::time::Duration;
+
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::{
+    ai::{Engine, EngineErgebnis, EngineOptionen},
+    brett::{Spieler, Spielstand},
+    pdn::{parse_fen, to_fen},
+};
+
+// Die Schnittstelle für JavaScript. Stellungen werden als FEN wie in PDN übergeben, Züge in
+// der Notation von `Zug::to_notation`, zum Beispiel `11-15` oder `15x24`.
+
+fn spieler_to_text(spieler: Spieler) -> String {
+    match spieler {
+        Spieler::Schwarz => "schwarz".to_string(),
+        Spieler::Weiss => "weiss".to_string(),
+    }
+}
+
+/// Das Ergebnis einer Suche für JavaScript
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct WasmErgebnis {
+    zug: String,
+    hauptvariante: Vec<String>,
+    /// Aus Sicht des Spielers am Zug in Hundertstel Steinen
+    pub bewertung: i32,
+    pub tiefe: u32,
+}
+
+#[wasm_bindgen]
+impl WasmErgebnis {
+    #[wasm_bindgen(getter)]
+    pub fn zug(&self) -> String {
+        self.zug.clone()
+    }
+
+    /// Die erwartete Fortsetzung, beginnend mit `zug`
+    #[wasm_bindgen(getter)]
+    pub fn hauptvariante(&self) -> Vec<String> {
+        self.hauptvariante.clone()
+    }
+}
+
+impl From<EngineErgebnis> for WasmErgebnis {
+    fn from(ergebnis: EngineErgebnis) -> Self {
+        Self {
+            zug: ergebnis.zug.to_notation(),
+            hauptvariante: ergebnis
+                .hauptvariante
+                .iter()
+                .map(|zug| zug.to_notation())
+                .collect(),
+            bewertung: ergebnis.bewertung,
+            tiefe: ergebnis.depth,
+        }
+    }
+}
+
+/// Eine Partie mit Regeln und Engine
+#[wasm_bindgen]
+#[derive(Clone, Debug, Default)]
+pub struct WasmPartie {
+    engine: Engine,
+}
+
+#[wasm_bindgen]
+impl WasmPartie {
+    /// Eine neue Partie in der Startaufstellung
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Eine Partie ab der Stellung `fen`, `undefined` bei einer ungültigen Stellung
+    pub fn from_fen(fen: &str) -> Option<WasmPartie> {
+        let (brett, am_zug) = parse_fen(fen)?;
+        let mut partie = Self::new();
+        partie.engine.set_position(brett, am_zug);
+        Some(partie)
+    }
+
+    pub fn fen(&self) -> String {
+        let partie = self.engine.get_partie();
+        to_fen(partie.get_brett(), partie.get_am_zug())
+    }
+
+    /// `schwarz` oder `weiss`
+    pub fn am_zug(&self) -> String {
+        spieler_to_text(self.engine.get_partie().get_am_zug())
+    }
+
+    /// `laeuft`, `remis` oder die Farbe des Gewinners
+    pub fn spielstand(&self) -> String {
+        match self.engine.get_partie().get_spielstand() {
+            Spielstand::Laeuft => "laeuft".to_string(),
+            Spielstand::Gewonnen(spieler) => spieler_to_text(spieler),
+            Spielstand::Remis(_) => "remis".to_string(),
+        }
+    }
+
+    /// Alle erlaubten Züge des Spielers am Zug, leer, wenn die Partie vorbei ist
+    pub fn legale_zuege(&self) -> Vec<String> {
+        let partie = self.engine.get_partie();
+        if partie.get_spielstand() != Spielstand::Laeuft {
+            return Vec::new();
+        }
+        partie
+            .get_brett()
+            .get_possible_zuege(partie.get_am_zug())
+            .iter()
+            .map(|(zug, _)| zug.to_notation())
+            .collect()
+    }
+
+    /// Führt den Zug aus, gibt `false` zurück, wenn er nicht erlaubt ist
+    pub fn ziehe(&mut self, notation: &str) -> bool {
+        let partie = self.engine.get_partie();
+        partie
+            .get_brett()
+            .find_zug(partie.get_am_zug(), notation)
+            .and_then(|nachher| self.engine.ziehe(nachher))
+            .is_some()
+    }
+
+    /// Sucht den besten Zug bis `tiefe`, mit `millis` höchstens ungefähr so lange. Gibt
+    /// `undefined` zurück, wenn die Partie vorbei ist.
+    pub fn bester_zug(&self, tiefe: u32, millis: Option<u32>) -> Option<WasmErgebnis> {
+        if self.engine.get_partie().get_spielstand() != Spielstand::Laeuft {
+            return None;
+        }
+        let mut engine = self.engine.clone();
+        engine.set_optionen(EngineOptionen {
+            max_depth: tiefe,
+            max_zeit: millis.map(|millis| Duration::from_millis(millis.into())),
+            ..EngineOptionen::default()
+        });
+        engine.suche(|_| {}).map(WasmErgebnis::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[wasm_bindgen_test]
+    fn test_wasm_partie() {
+        let mut partie = WasmPartie::new();
+        assert_eq!(partie.am_zug(), "schwarz");
+        assert_eq!(partie.legale_zuege().len(), 7);
+        assert!(partie.ziehe("11-15"));
+        assert!(!partie.ziehe("11-15"));
+        assert!(partie.ziehe("24-20"));
+        assert_eq!(partie.legale_zuege(), vec!["15x24"]);
+
+        let ergebnis = partie.bester_zug(4, Some(1000)).unwrap();
+        assert_eq!(ergebnis.zug(), "15x24");
+        assert_eq!(ergebnis.tiefe, 4);
+        assert_eq!(ergebnis.hauptvariante()[0], "15x24");
+
+        let kopie = WasmPartie::from_fen(&partie.fen()).unwrap();
+        assert_eq!(kopie.fen(), partie.fen());
+        assert!(WasmPartie::from_fen("kein fen").is_none());
+    }
+}